@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result, bail};
+
+const USAGE: &str = "Usage: aoc [calendar]
+       aoc run <year>[/<day>] [-- <args>...]
+       aoc record <year>/<day>";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Status {
+    Solved,
+    Partial,
+    Missing,
+}
+
+// An `event-YYYY` crate in the workspace, with the answers recorded for its days
+#[derive(Debug)]
+struct Year {
+    year: u32,
+    dir: PathBuf,
+    answers: BTreeMap<u32, Vec<String>>,
+}
+
+impl Year {
+    // Events had 25 days until 2025, which had 12
+    fn num_days(&self) -> u32 {
+        if self.year < 2025 { 25 } else { 12 }
+    }
+
+    fn bin(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("src/bin/day{day:02}.rs"))
+    }
+
+    fn answers_path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("answers/day{day:02}.txt"))
+    }
+
+    fn read_answers(&mut self) -> Result<()> {
+        for day in 1..=self.num_days() {
+            let path = self.answers_path(day);
+            if !path.is_file() {
+                continue;
+            }
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read '{}'", path.display()))?;
+            self.answers.insert(day, answers(&contents));
+        }
+        Ok(())
+    }
+
+    // A day is solved once every part has a recorded answer, and the last day only has
+    // one part
+    fn status(&self, day: u32) -> Status {
+        let parts = if day == self.num_days() { 1 } else { 2 };
+        match self.answers.get(&day).map_or(0, Vec::len) {
+            0 => Status::Missing,
+            n if n >= parts => Status::Solved,
+            _ => Status::Partial,
+        }
+    }
+
+    // Solutions read their inputs relative to the top of the workspace
+    fn cargo_run(&self, workspace: &Path, day: u32, args: &[String]) -> Result<Command> {
+        if !self.bin(day).is_file() {
+            bail!("No solution for {}/{day}", self.year);
+        }
+        let mut command = Command::new("cargo");
+        command
+            .args(["run", "-q", "-p", &format!("event-{}", self.year)])
+            .args(["--bin", &format!("day{day:02}"), "--"])
+            .args(args)
+            .current_dir(workspace);
+        Ok(command)
+    }
+}
+
+// The answers in a file written by `record`, one per line
+fn answers(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// The workspace around the current directory, so the calendar is for the checkout it's
+// run in rather than the one `aoc` was built in
+fn workspace() -> Result<PathBuf> {
+    let output = Command::new("cargo")
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .stderr(Stdio::inherit())
+        .output()
+        .context("Failed to run cargo")?;
+    if !output.status.success() {
+        bail!("Failed to find the workspace");
+    }
+    let manifest = PathBuf::from(String::from_utf8(output.stdout)?.trim());
+    manifest
+        .parent()
+        .map(Path::to_path_buf)
+        .context("Workspace manifest has no directory")
+}
+
+// Every `event-YYYY` directory with a manifest, oldest first
+fn years(workspace: &Path) -> Result<Vec<Year>> {
+    let entries = fs::read_dir(workspace)
+        .with_context(|| format!("Failed to read '{}'", workspace.display()))?;
+
+    let mut years: Vec<Year> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let year = name.strip_prefix("event-")?.parse().ok()?;
+            let dir = entry.path();
+            dir.join("Cargo.toml").is_file().then_some(Year {
+                year,
+                dir,
+                answers: BTreeMap::new(),
+            })
+        })
+        .collect();
+    years.sort_by_key(|year| year.year);
+    for year in &mut years {
+        year.read_answers()?;
+    }
+    Ok(years)
+}
+
+// A `<year>[/<day>]` argument, along with that year's crate
+fn target<'a>(years: &'a [Year], target: &str) -> Result<(&'a Year, Option<u32>)> {
+    let (year, day) = match target.split_once('/') {
+        Some((year, day)) => (year, Some(day)),
+        None => (target, None),
+    };
+    let year: u32 = year.parse().context("Year should be a number")?;
+    let day: Option<u32> = day
+        .map(|day| day.parse().context("Day should be a number"))
+        .transpose()?;
+
+    let Some(year) = years.iter().find(|y| y.year == year) else {
+        bail!("No crate for {year}, create it with 'cargo new event-{year}'");
+    };
+    Ok((year, day))
+}
+
+// One row per year with a column per day: `**` for solved, `* ` for started and `. ` for
+// missing, followed by the number of stars
+fn calendar(years: &[Year]) -> String {
+    let mut result = String::new();
+    let max_days = years.iter().map(Year::num_days).max().unwrap_or(0);
+
+    write!(result, "    ").unwrap();
+    for day in 1..=max_days {
+        write!(result, " {day:>2}").unwrap();
+    }
+    writeln!(result).unwrap();
+
+    for year in years {
+        write!(result, "{}", year.year).unwrap();
+        let mut stars = 0;
+        for day in 1..=year.num_days() {
+            let cell = match year.status(day) {
+                Status::Solved => {
+                    stars += 2;
+                    "**"
+                }
+                Status::Partial => {
+                    stars += 1;
+                    "* "
+                }
+                Status::Missing => ". ",
+            };
+            write!(result, " {cell}").unwrap();
+        }
+        let padding = 3 * (max_days - year.num_days()) as usize;
+        writeln!(result, "{:padding$}  {stars}/{}", "", 2 * year.num_days()).unwrap();
+    }
+    result
+}
+
+// Runs one day, or every day of a year that has a solution, through cargo
+fn run(workspace: &Path, target_arg: &str, args: &[String]) -> Result<()> {
+    let years = years(workspace)?;
+    let (year, day) = target(&years, target_arg)?;
+
+    let days: Vec<u32> = match day {
+        Some(day) => vec![day],
+        None => (1..=year.num_days())
+            .filter(|&day| year.bin(day).is_file())
+            .collect(),
+    };
+
+    for &day in &days {
+        let mut command = year.cargo_run(workspace, day, args)?;
+        if days.len() > 1 {
+            println!("--- {}/{day}", year.year);
+        }
+        let status = command.status().context("Failed to run cargo")?;
+        if !status.success() {
+            bail!("{}/{day} failed", year.year);
+        }
+    }
+    Ok(())
+}
+
+// Saves the answers a day prints, once they've been accepted, so the calendar counts
+// its stars
+fn record(workspace: &Path, target_arg: &str) -> Result<()> {
+    let years = years(workspace)?;
+    let (year, day) = target(&years, target_arg)?;
+    let Some(day) = day else {
+        bail!(USAGE);
+    };
+
+    let output = year
+        .cargo_run(workspace, day, &[])?
+        .stderr(Stdio::inherit())
+        .output()
+        .context("Failed to run cargo")?;
+    if !output.status.success() {
+        bail!("{}/{day} failed", year.year);
+    }
+    let answers = answers(&String::from_utf8(output.stdout)?);
+    if answers.is_empty() {
+        bail!("{}/{day} didn't print any answers", year.year);
+    }
+
+    let path = year.answers_path(day);
+    fs::create_dir_all(path.parent().unwrap())
+        .with_context(|| format!("Failed to create '{}'", path.display()))?;
+    fs::write(&path, answers.join("\n") + "\n")
+        .with_context(|| format!("Failed to write '{}'", path.display()))?;
+    println!("Recorded {} answers to '{}'", answers.len(), path.display());
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        None | Some("calendar") => {
+            print!("{}", calendar(&years(&workspace()?)?));
+            Ok(())
+        }
+        Some("run") => {
+            let target = args.get(1).context(USAGE)?;
+            let rest = match args.get(2).map(String::as_str) {
+                Some("--") => &args[3..],
+                Some(_) => bail!(USAGE),
+                None => &[],
+            };
+            run(&workspace()?, target, rest)
+        }
+        Some("record") => {
+            let target = args.get(1).context(USAGE)?;
+            if args.len() > 2 {
+                bail!(USAGE);
+            }
+            record(&workspace()?, target)
+        }
+        _ => bail!(USAGE),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn year(year: u32, answers: &[(u32, &str)]) -> Year {
+        Year {
+            year,
+            dir: PathBuf::from(format!("event-{year}")),
+            answers: answers
+                .iter()
+                .map(|&(day, contents)| (day, super::answers(contents)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn calendar_counts_recorded_answers() {
+        let years = [
+            year(2024, &[(1, "12\n34\n"), (2, "56\n"), (3, "\n")]),
+            year(2025, &[(1, "12\n34\n"), (12, "78\n")]),
+        ];
+        assert_eq!(years[0].status(1), Status::Solved);
+        assert_eq!(years[0].status(2), Status::Partial);
+        assert_eq!(years[0].status(3), Status::Missing);
+        assert_eq!(years[0].status(4), Status::Missing);
+        assert_eq!(years[1].status(12), Status::Solved);
+
+        let calendar = calendar(&years);
+        let lines: Vec<&str> = calendar.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("2024 ** *  . "));
+        assert!(lines[1].ends_with("  3/50"));
+        assert!(lines[2].ends_with("  4/24"));
+        assert_eq!(lines[1].len(), lines[2].len());
+    }
+
+    #[test]
+    fn targets_name_a_year_and_maybe_a_day() {
+        let years = [year(2025, &[])];
+        let (year, day) = target(&years, "2025/3").unwrap();
+        assert_eq!((year.year, day), (2025, Some(3)));
+        assert_eq!(target(&years, "2025").unwrap().1, None);
+        assert!(target(&years, "2024").is_err());
+        assert!(target(&years, "2025/x").is_err());
+    }
+}
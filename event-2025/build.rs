@@ -0,0 +1,42 @@
+use std::fs;
+use std::path::Path;
+
+// With `embed-inputs`, `aoc_day!` includes a day's input through `input-$day.rs`, as it only
+// has the day number and not the file name
+fn main() {
+    println!("cargo::rerun-if-changed=src");
+    if std::env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+    println!("cargo::rerun-if-changed=input");
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let src = Path::new(&manifest_dir).join("src");
+    let mut days: Vec<String> = fs::read_dir(&src)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|name| Some(name.strip_prefix("day")?.strip_suffix(".rs")?.to_string()))
+        .collect();
+    days.sort();
+
+    for number in &days {
+        let name = format!("day{number}");
+        let day: u32 = number.parse().unwrap();
+        let input = Path::new(&manifest_dir).join(format!("input/{name}.txt"));
+        let contents = if input.is_file() {
+            format!("include_str!({input:?})")
+        } else {
+            format!(
+                "compile_error!(\"No input for {name} at {}\")",
+                input.display()
+            )
+        };
+        fs::write(
+            Path::new(&out_dir).join(format!("input-{day}.rs")),
+            contents,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,417 @@
+use std::fmt;
+
+use anyhow::{Context, Result, anyhow, bail};
+use chumsky::prelude::*;
+use util::{InputFile, Spanned};
+
+#[derive(Debug, Copy, Clone)]
+enum Direction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(anyhow!("Invalid direction character '{}'", value)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Rotation {
+    direction: Direction,
+    distance: i32,
+}
+
+impl Rotation {
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        one_of("LR")
+            .map(Direction::try_from)
+            .unwrapped()
+            .then(util::unsigned::<i32>(10))
+            .map(|(direction, distance)| Self {
+                direction,
+                distance,
+            })
+    }
+
+    fn amount(&self) -> i64 {
+        match self.direction {
+            Direction::Left => -i64::from(self.distance),
+            Direction::Right => i64::from(self.distance),
+        }
+    }
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+        };
+        write!(f, "{direction}{}", self.distance)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Dial {
+    size: i64,
+    start: i64,
+    target: i64,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Self {
+            size: 100,
+            start: 50,
+            target: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    rotation: String,
+    // Where the dial would be if it never wrapped around
+    position: i64,
+    quotient: i64,
+    dial: i64,
+    crossings: u32,
+    landed: bool,
+}
+
+impl Dial {
+    // How many multiples of `size` offset by `target` are at most `x`, up to a constant
+    fn target_index(&self, x: i64) -> i64 {
+        (x - self.target).div_euclid(self.size)
+    }
+
+    // Counts the times the dial points at the target while moving by `amount` from the
+    // unwrapped position `from`, not counting where it starts.
+    fn crossings(&self, from: i64, amount: i64) -> u32 {
+        let to = from + amount;
+        let count = if amount >= 0 {
+            self.target_index(to) - self.target_index(from)
+        } else {
+            self.target_index(from - 1) - self.target_index(to - 1)
+        };
+        count.try_into().unwrap()
+    }
+
+    fn trace(&self, rotations: &[Spanned<Rotation>]) -> Vec<Step> {
+        rotations
+            .iter()
+            .scan(self.start, |position, rotation| {
+                let from = *position;
+                *position += rotation.amount();
+                Some(Step {
+                    rotation: rotation.to_string(),
+                    position: *position,
+                    quotient: position.div_euclid(self.size),
+                    dial: position.rem_euclid(self.size),
+                    crossings: self.crossings(from, rotation.amount()),
+                    landed: position.rem_euclid(self.size) == self.target,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum TraceFormat {
+    Table,
+    Csv,
+}
+
+fn print_trace(steps: &[Step], format: TraceFormat) {
+    let header = [
+        "rotation",
+        "position",
+        "quotient",
+        "dial",
+        "crossings",
+        "landed",
+    ];
+    let rows = steps.iter().map(|step| {
+        [
+            step.rotation.clone(),
+            step.position.to_string(),
+            step.quotient.to_string(),
+            step.dial.to_string(),
+            step.crossings.to_string(),
+            step.landed.to_string(),
+        ]
+    });
+
+    match format {
+        TraceFormat::Csv => {
+            println!("{}", header.join(","));
+            for row in rows {
+                println!("{}", row.join(","));
+            }
+        }
+        TraceFormat::Table => {
+            println!("{}", header.map(|h| format!("{h:>9}")).join(" "));
+            for row in rows {
+                println!("{}", row.map(|v| format!("{v:>9}")).join(" "));
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    rotations: Vec<Spanned<Rotation>>,
+}
+
+impl Input {
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        util::spanned(Rotation::parser())
+            .separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>()
+            .map(|rotations| Self { rotations })
+    }
+
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn part_one(&self, dial: &Dial) -> usize {
+        dial.trace(&self.rotations)
+            .iter()
+            .filter(|step| step.landed)
+            .count()
+    }
+
+    fn part_two(&self, dial: &Dial) -> u32 {
+        dial.trace(&self.rotations)
+            .iter()
+            .map(|step| step.crossings)
+            .sum()
+    }
+}
+
+struct Args {
+    dial: Dial,
+    trace: Option<TraceFormat>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut result = Self {
+            dial: Dial::default(),
+            trace: None,
+        };
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| -> Result<i64> {
+                let value = args
+                    .next()
+                    .with_context(|| format!("Missing value for '{name}'"))?;
+                value
+                    .parse()
+                    .with_context(|| format!("Invalid value '{value}' for '{name}'"))
+            };
+
+            match arg.as_str() {
+                "--size" => result.dial.size = value("--size")?,
+                "--start" => result.dial.start = value("--start")?,
+                "--target" => result.dial.target = value("--target")?,
+                "--trace" => result.trace = Some(TraceFormat::Table),
+                "--csv" => result.trace = Some(TraceFormat::Csv),
+                _ => bail!("Unknown argument '{arg}'"),
+            }
+        }
+
+        let dial = result.dial;
+        if dial.size <= 0 {
+            bail!("Dial size should be positive");
+        }
+        if !(0..dial.size).contains(&dial.start) || !(0..dial.size).contains(&dial.target) {
+            bail!(
+                "Dial start and target should be between 0 and {}",
+                dial.size - 1
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(usize, u32)> {
+    let input = Input::parse(input_file)?;
+    let dial = Dial::default();
+    Ok((input.part_one(&dial), input.part_two(&dial)))
+}
+
+// `--trace` prints every rotation, and `--size`, `--start` and `--target` change the dial
+fn cli(input_file: &InputFile, args: &[String]) -> Result<()> {
+    let input = Input::parse(input_file)?;
+    let args = Args::parse(args.iter().cloned())?;
+
+    if let Some(format) = args.trace {
+        print_trace(&args.dial.trace(&input.rotations), format);
+        return Ok(());
+    }
+
+    println!("{}", input.part_one(&args.dial));
+    println!("{}", input.part_two(&args.dial));
+
+    Ok(())
+}
+
+util::aoc_day!(2025, 1, solve, cli);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const EXAMPLE: &str = indoc! {"
+        L68
+        L30
+        R48
+        L5
+        R60
+        L55
+        L1
+        L99
+        R14
+        L82
+    "};
+
+    #[test]
+    fn large_amount_works() {
+        let input = Input::parse(&"R1000".into()).unwrap();
+        assert_eq!(input.part_two(&Dial::default()), 10);
+
+        let input = Input::parse(&"R1050\nL1050".into()).unwrap();
+        assert_eq!(input.part_two(&Dial::default()), 21);
+    }
+
+    #[test]
+    fn at_zero_works() {
+        let input = Input::parse(&"L50\nL500".into()).unwrap();
+        assert_eq!(input.part_two(&Dial::default()), 6);
+
+        let input = Input::parse(&"R50\nR500".into()).unwrap();
+        assert_eq!(input.part_two(&Dial::default()), 6);
+
+        let input = Input::parse(&"L25\nL25\nR25".into()).unwrap();
+        assert_eq!(input.part_two(&Dial::default()), 1);
+
+        let input = Input::parse(&"R50\nR0".into()).unwrap();
+        assert_eq!(input.part_two(&Dial::default()), 1);
+    }
+
+    #[test]
+    fn trace_works() {
+        let input = Input::parse(&"L50\nL500\nR3".into()).unwrap();
+        let trace = Dial::default().trace(&input.rotations);
+        let crossings: Vec<_> = trace.iter().map(|step| step.crossings).collect();
+        assert_eq!(crossings, vec![1, 5, 0]);
+        assert_eq!(
+            trace[1],
+            Step {
+                rotation: "L500".to_string(),
+                position: -500,
+                quotient: -5,
+                dial: 0,
+                crossings: 5,
+                landed: true,
+            }
+        );
+    }
+
+    #[test]
+    fn crossings_match_clicking() {
+        let dials = [
+            Dial::default(),
+            Dial {
+                size: 7,
+                start: 3,
+                target: 5,
+            },
+            Dial {
+                size: 1,
+                start: 0,
+                target: 0,
+            },
+        ];
+        for dial in dials {
+            for from in -20..20 {
+                for amount in -30_i64..30 {
+                    let step = amount.signum();
+                    let expected = (1..=amount.abs())
+                        .filter(|i| (from + i * step).rem_euclid(dial.size) == dial.target)
+                        .count();
+                    assert_eq!(
+                        dial.crossings(from, amount) as usize,
+                        expected,
+                        "{dial:?} from {from} by {amount}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn other_dials_work() {
+        let input = Input::parse(&"R5\nL12\nR30".into()).unwrap();
+        let dial = Dial {
+            size: 10,
+            start: 5,
+            target: 3,
+        };
+        // Moving to 10, then -2, then 28 passes 3, then 3, 13 and 23
+        assert_eq!(input.part_one(&dial), 0);
+        assert_eq!(input.part_two(&dial), 4);
+    }
+
+    #[test]
+    fn args_work() {
+        let args = |s: &str| Args::parse(s.split_whitespace().map(String::from));
+        let parsed = args("--size 10 --start 5 --csv").unwrap();
+        assert_eq!(parsed.dial.size, 10);
+        assert_eq!(parsed.dial.start, 5);
+        assert_eq!(parsed.trace, Some(TraceFormat::Csv));
+
+        assert!(args("--size 0").is_err());
+        assert!(args("--size 10").is_err());
+        assert!(args("--target").is_err());
+        assert!(args("--bogus").is_err());
+    }
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one(&Dial::default()), 3);
+        assert_eq!(example.part_two(&Dial::default()), 6);
+    }
+
+    util::fuzz_tests! {
+        name: "day01",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let dial = Dial::default();
+            let _ = input.part_one(&dial);
+            let _ = input.part_two(&dial);
+        },
+    }
+}
@@ -0,0 +1,345 @@
+use anyhow::{Result, anyhow};
+use chumsky::prelude::*;
+use util::{InputFile, Spanned, parallel};
+
+#[derive(Debug, Copy, Clone)]
+struct Range {
+    low: u64,
+    hi: u64,
+}
+
+impl Range {
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        util::unsigned::<u64>(10)
+            .then_ignore(just("-"))
+            .then(util::unsigned::<u64>(10))
+            .map(|(low, hi)| Self { low, hi })
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    ranges: Vec<Spanned<Range>>,
+}
+
+impl Input {
+    const MAX_DIGITS: u32 = u64::MAX.ilog10() + 1;
+
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        util::spanned(Range::parser())
+            .separated_by(just(","))
+            .collect::<Vec<_>>()
+            .map(|ranges| Self { ranges })
+            .then_ignore(text::newline().or_not())
+            .then_ignore(end())
+    }
+
+    #[cfg(test)]
+    fn is_invalid(id: u64) -> bool {
+        let num_digits = id.max(1).ilog10() + 1;
+        if num_digits.is_multiple_of(2) {
+            let pow_10 = 10_u64.pow(num_digits / 2);
+            id % pow_10 == id / pow_10
+        } else {
+            false
+        }
+    }
+
+    #[cfg(test)]
+    fn is_invalid2(id: u64) -> bool {
+        let num_digits = id.max(1).ilog10() + 1;
+        for d in 1..=num_digits / 2 {
+            if !num_digits.is_multiple_of(d) {
+                continue;
+            }
+            let k = num_digits / d;
+            // Construct pattern of `k` ones each spaced apart by `d - 1` zeros
+            let pattern = (10_u128.pow(k * d) - 1) / (10_u128.pow(d) - 1);
+            if u128::from(id).is_multiple_of(pattern) {
+                return true;
+            }
+        }
+        false
+    }
+
+    #[cfg(test)]
+    fn brute_force(&self, is_invalid: fn(u64) -> bool) -> u128 {
+        self.ranges
+            .iter()
+            .flat_map(|range| (range.low..=range.hi).filter(|&x| is_invalid(x)))
+            .map(u128::from)
+            .sum()
+    }
+
+    // Sums the `num_digits`-digit numbers in the range made of one `block_len`-digit block
+    // repeated. Each is the block times a pattern of ones spaced `block_len` apart, so the
+    // blocks that land in the range are consecutive and sum as an arithmetic series.
+    fn sum_repeated(range: &Range, num_digits: u32, block_len: u32) -> u128 {
+        let low = u128::from(range.low).max(10_u128.pow(num_digits - 1));
+        let hi = u128::from(range.hi).min(10_u128.pow(num_digits) - 1);
+        let pattern = (10_u128.pow(num_digits) - 1) / (10_u128.pow(block_len) - 1);
+
+        let first = low.div_ceil(pattern).max(10_u128.pow(block_len - 1));
+        let last = (hi / pattern).min(10_u128.pow(block_len) - 1);
+
+        if low > hi || first > last {
+            return 0;
+        }
+
+        pattern * (first + last) * (last - first + 1) / 2
+    }
+
+    fn mobius(mut n: u32) -> i32 {
+        let mut result = 1;
+        let mut p = 2;
+        while p * p <= n {
+            if n.is_multiple_of(p) {
+                n /= p;
+                if n.is_multiple_of(p) {
+                    return 0;
+                }
+                result = -result;
+            }
+            p += 1;
+        }
+        if n > 1 { -result } else { result }
+    }
+
+    // A number is made of repeated blocks exactly when it repeats some block of length
+    // `num_digits / p` for a prime `p`. Numbers repeating several of those also repeat
+    // the block of their gcd, so inclusion-exclusion over the squarefree divisors `m`
+    // counts each number once.
+    fn sum_any_repeated(range: &Range, num_digits: u32) -> u128 {
+        let mut total = 0_i128;
+        for m in 2..=num_digits {
+            if num_digits.is_multiple_of(m) {
+                let sum = Self::sum_repeated(range, num_digits, num_digits / m) as i128;
+                total -= i128::from(Self::mobius(m)) * sum;
+            }
+        }
+        total.try_into().unwrap()
+    }
+
+    fn part_one(&self) -> u128 {
+        let sums = parallel::map(&self.ranges, |_, range| {
+            (2..=Self::MAX_DIGITS)
+                .step_by(2)
+                .map(|n| Self::sum_repeated(range, n, n / 2))
+                .sum::<u128>()
+        });
+        sums.into_iter().sum()
+    }
+
+    fn part_two(&self) -> u128 {
+        let sums = parallel::map(&self.ranges, |_, range| {
+            (2..=Self::MAX_DIGITS)
+                .map(|n| Self::sum_any_repeated(range, n))
+                .sum::<u128>()
+        });
+        sums.into_iter().sum()
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(u128, u128)> {
+    let input = Input::parse(input_file)?;
+    Ok((input.part_one(), input.part_two()))
+}
+
+util::aoc_day!(2025, 2, solve);
+
+// Checks every ID, for comparing against `solve` on generated inputs
+#[cfg(test)]
+pub(crate) fn reference(input_file: &InputFile) -> Result<Vec<String>> {
+    let input = Input::parse(input_file)?;
+    let answers = (
+        input.brute_force(Input::is_invalid),
+        input.brute_force(Input::is_invalid2),
+    );
+    Ok(util::day::Answers::answers(answers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use util::differential::Differential;
+    use util::digit_dp::{self, DigitAutomaton};
+    use util::rng::Rng;
+
+    const EXAMPLE: &str = indoc! {"
+        11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124
+    "};
+
+    #[test]
+    fn is_invalid_works() {
+        assert!(Input::is_invalid(11));
+        assert!(Input::is_invalid(22));
+        assert!(Input::is_invalid(99));
+        assert!(Input::is_invalid(1010));
+        assert!(Input::is_invalid(1188511885));
+        assert!(Input::is_invalid(222222));
+
+        assert!(!Input::is_invalid(0));
+        assert!(!Input::is_invalid(9));
+        assert!(!Input::is_invalid(10));
+    }
+
+    #[test]
+    fn is_invalid2_works() {
+        assert!(Input::is_invalid2(11));
+        assert!(Input::is_invalid2(22));
+        assert!(Input::is_invalid2(99));
+        assert!(Input::is_invalid2(1010));
+        assert!(Input::is_invalid2(1188511885));
+        assert!(Input::is_invalid2(222222));
+
+        assert!(Input::is_invalid2(565656));
+        assert!(Input::is_invalid2(824824824));
+        assert!(Input::is_invalid2(2121212121));
+    }
+
+    // A few ranges short enough to check every ID, with IDs of up to ten digits
+    fn small_ranges(rng: &mut Rng) -> String {
+        let ranges: Vec<String> = (0..rng.range(1..=4))
+            .map(|_| {
+                let digits = rng.range(1..=10) as u32;
+                let low = rng.range(1..=10_u64.pow(digits));
+                format!("{low}-{}", low + rng.range(0..=2000))
+            })
+            .collect();
+        ranges.join(",") + "\n"
+    }
+
+    fn parse_small(contents: &str) -> Option<Input> {
+        let input = Input::parser().parse(contents).into_result().ok()?;
+        let small = input
+            .ranges
+            .iter()
+            .all(|r| r.hi.saturating_sub(r.low) <= 100_000);
+        small.then_some(input)
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force_on_random_ranges() {
+        Differential {
+            name: "day02",
+            generate: small_ranges,
+            reference: |contents| {
+                let input = parse_small(contents)?;
+                Some((
+                    input.brute_force(Input::is_invalid),
+                    input.brute_force(Input::is_invalid2),
+                ))
+            },
+            optimized: |contents| {
+                let input = parse_small(contents)?;
+                Some((input.part_one(), input.part_two()))
+            },
+        }
+        .run(1000, &crate::regressions_dir());
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force() {
+        let contents = "1-99999,1000-1000,99-101,123123-123123,1-1,1111110-1111112";
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one(), input.brute_force(Input::is_invalid));
+        assert_eq!(input.part_two(), input.brute_force(Input::is_invalid2));
+    }
+
+    // Part one as a digit automaton: remember the first half, then match it digit by digit
+    struct RepeatedHalves;
+
+    impl DigitAutomaton for RepeatedHalves {
+        type State = (usize, Vec<u32>);
+
+        fn start(&self, len: usize) -> Self::State {
+            (len, Vec::new())
+        }
+
+        fn step(&self, state: &Self::State, position: usize, digit: u32) -> Option<Self::State> {
+            let (len, half) = state;
+            if len % 2 == 1 {
+                None
+            } else if position < len / 2 {
+                let mut half = half.clone();
+                half.push(digit);
+                Some((*len, half))
+            } else {
+                (half[position - len / 2] == digit).then(|| state.clone())
+            }
+        }
+
+        fn accepts(&self, _state: &Self::State) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn closed_form_matches_digit_dp() {
+        let contents = "11-22,95-115,998-1012,1188511880-1188511890,1-9999999";
+        let input = Input::parse(&contents.into()).unwrap();
+        let expected: u128 = input
+            .ranges
+            .iter()
+            .map(|r| digit_dp::tally(&RepeatedHalves, 10, r.low.into(), r.hi.into()).sum)
+            .sum();
+        assert_eq!(input.part_one(), expected);
+    }
+
+    #[test]
+    fn huge_range_works() {
+        // Ranges at the top of the u64 range, short enough to check every ID, including
+        // 18446744071844674407, the largest number that is one block repeated twice
+        let contents =
+            "18446744071844674000-18446744071844675000,18446744073709550615-18446744073709551615";
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one(), input.brute_force(Input::is_invalid));
+        assert_eq!(input.part_two(), input.brute_force(Input::is_invalid2));
+        assert_eq!(input.part_one(), 18446744071844674407);
+
+        // Every k-digit block b repeated twice is b * (10^k + 1), so the blocks that fit
+        // sum as arithmetic series
+        let input = Input::parse(&"1-18446744073709551615".into()).unwrap();
+        let expected: u128 = (1..=10)
+            .map(|k| {
+                let pattern = 10_u128.pow(k) + 1;
+                let first = 10_u128.pow(k - 1);
+                let last = (10_u128.pow(k) - 1).min(u128::from(u64::MAX) / pattern);
+                pattern * (first + last) * (last - first + 1) / 2
+            })
+            .sum();
+        assert_eq!(expected, 12509613850169742155792778978);
+        assert_eq!(input.part_one(), expected);
+        assert!(input.part_two() > input.part_one());
+    }
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one(), 1227775554);
+        assert_eq!(example.part_two(), 4174379265);
+    }
+
+    util::fuzz_tests! {
+        name: "day02",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
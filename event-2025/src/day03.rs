@@ -0,0 +1,345 @@
+use anyhow::{Context, Result, anyhow, bail};
+use chumsky::prelude::*;
+use util::{InputFile, parallel};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        TooFewBatteries { bank: usize, digits: usize } =>
+            "bank {bank} has fewer than {digits} batteries",
+        JoltageOverflow { bank: usize } => "total joltage overflows at bank {bank}",
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    banks: Vec<Vec<u64>>,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        text::digits(10)
+            .to_slice()
+            .map(|x: &str| {
+                x.chars()
+                    .map(|c| u64::from(c.to_digit(10).unwrap()))
+                    .collect::<Vec<_>>()
+            })
+            .separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>()
+            .map(|banks| Self { banks })
+    }
+
+    #[cfg(test)]
+    fn max_joltage(ratings: &[u64], digits: usize) -> u64 {
+        if digits == 0 {
+            return 0;
+        }
+        // Use a greedy approach. We want the largest digit the farthest to the left.
+        // This means that if there isn't enough digits to the right, then compute a
+        // "deficit", which is the smallest number of digits to the left we need so that
+        // deficit + need_right + 1 == digits.
+        for d in (0..=9).rev() {
+            if let Some(pos) = ratings.iter().position(|&x| x == d) {
+                let num_right = ratings.len() - pos - 1;
+                let deficit = (digits - 1).saturating_sub(num_right);
+                let need_right = (digits - 1) - deficit;
+                let left = Self::max_joltage(&ratings[..pos], deficit);
+                let right = Self::max_joltage(&ratings[pos + 1..], need_right);
+                return 10u64.pow(need_right.try_into().unwrap()) * (10 * left + d) + right;
+            }
+        }
+        unreachable!("ratings should have digits");
+    }
+
+    #[cfg(test)]
+    fn max_total_joltage(&self, digits: usize) -> u128 {
+        self.banks
+            .iter()
+            .map(|bank| u128::from(Self::max_joltage(bank, digits)))
+            .sum()
+    }
+
+    // Keeps a stack of chosen positions and pops the top whenever the incoming digit should
+    // come before it, as long as enough digits remain to still pick `digits` of them.
+    fn select(ratings: &[u64], digits: usize, before: impl Fn(u64, u64) -> bool) -> Vec<usize> {
+        assert!(
+            digits <= ratings.len(),
+            "bank should have at least {digits} batteries"
+        );
+
+        let mut drops = ratings.len() - digits;
+        let mut stack: Vec<usize> = Vec::with_capacity(ratings.len());
+        for (i, &x) in ratings.iter().enumerate() {
+            while drops > 0 && stack.last().is_some_and(|&top| before(x, ratings[top])) {
+                stack.pop();
+                drops -= 1;
+            }
+            stack.push(i);
+        }
+
+        stack.truncate(digits);
+        stack
+    }
+
+    // Positions of the batteries giving the largest joltage, in order
+    fn select_largest(ratings: &[u64], digits: usize) -> Vec<usize> {
+        Self::select(ratings, digits, |x, top| x > top)
+    }
+
+    // Positions of the batteries giving the smallest joltage, in order
+    fn select_smallest(ratings: &[u64], digits: usize) -> Vec<usize> {
+        Self::select(ratings, digits, |x, top| x < top)
+    }
+
+    // Returns `None` if the joltage doesn't fit in a u128
+    fn joltage(ratings: &[u64], positions: &[usize]) -> Option<u128> {
+        positions.iter().try_fold(0_u128, |acc, &i| {
+            acc.checked_mul(10)?.checked_add(ratings[i].into())
+        })
+    }
+
+    fn total_joltage(
+        &self,
+        digits: usize,
+        select: fn(&[u64], usize) -> Vec<usize>,
+    ) -> Result<u128, Error> {
+        let joltages = parallel::map(&self.banks, |i, bank| {
+            if bank.len() < digits {
+                return Err(Error::TooFewBatteries {
+                    bank: i + 1,
+                    digits,
+                });
+            }
+            Self::joltage(bank, &select(bank, digits)).ok_or(Error::JoltageOverflow { bank: i + 1 })
+        });
+
+        // Added up in order, so the first bank to fail is the one reported
+        joltages
+            .into_iter()
+            .enumerate()
+            .try_fold(0_u128, |total, (i, joltage)| {
+                total
+                    .checked_add(joltage?)
+                    .ok_or(Error::JoltageOverflow { bank: i + 1 })
+            })
+    }
+
+    fn part_one(&self) -> Result<u128, Error> {
+        self.total_joltage(2, Self::select_largest)
+    }
+
+    fn part_two(&self) -> Result<u128, Error> {
+        self.total_joltage(12, Self::select_largest)
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(u128, u128)> {
+    let input = Input::parse(input_file)?;
+    let part_one = input.part_one().context("Failed to solve part one")?;
+    let part_two = input.part_two().context("Failed to solve part two")?;
+    Ok((part_one, part_two))
+}
+
+fn cli(input_file: &InputFile, args: &[String]) -> Result<()> {
+    let input = Input::parse(input_file)?;
+    if let Some(extra) = args.get(1) {
+        bail!("Unexpected argument '{extra}'");
+    }
+
+    match args[0].as_str() {
+        // Same digit counts as the two parts, but turning on the weakest batteries
+        "--smallest" => {
+            let part_one = input.total_joltage(2, Input::select_smallest);
+            println!("{}", part_one.context("Failed to solve part one")?);
+            let part_two = input.total_joltage(12, Input::select_smallest);
+            println!("{}", part_two.context("Failed to solve part two")?);
+        }
+        arg => bail!("Unknown argument '{arg}'"),
+    }
+
+    Ok(())
+}
+
+util::aoc_day!(2025, 3, solve, cli);
+
+// Picks the batteries greedily, for comparing against `solve` on generated inputs
+#[cfg(test)]
+pub(crate) fn reference(input_file: &InputFile) -> Result<Vec<String>> {
+    let input = Input::parse(input_file)?;
+    let answers = (input.max_total_joltage(2), input.max_total_joltage(12));
+    Ok(util::day::Answers::answers(answers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use itertools::Itertools;
+    use util::differential::Differential;
+    use util::rng::Rng;
+
+    const EXAMPLE: &str = indoc! {"
+        987654321111111
+        811111111111119
+        234234234234278
+        818181911112111
+    "};
+
+    // Every bank over `alphabet` with up to `max_len` batteries
+    fn small_banks(alphabet: &[u64], max_len: usize) -> impl Iterator<Item = Vec<u64>> + '_ {
+        (1..=max_len).flat_map(move |len| {
+            std::iter::repeat_n(alphabet.iter().copied(), len).multi_cartesian_product()
+        })
+    }
+
+    #[test]
+    fn select_largest_matches_recursive() {
+        for bank in small_banks(&[1, 2, 3], 6).chain(small_banks(&[0, 5, 9], 5)) {
+            for digits in 0..=bank.len() {
+                let positions = Input::select_largest(&bank, digits);
+                assert!(positions.is_sorted(), "bank {bank:?}");
+                assert_eq!(
+                    Input::joltage(&bank, &positions),
+                    Some(Input::max_joltage(&bank, digits).into()),
+                    "bank {bank:?} with {digits} digits"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cli_rejects_other_arguments() {
+        let args = |args: &str| args.split(' ').map(str::to_string).collect::<Vec<_>>();
+        let file = InputFile::from(EXAMPLE);
+        assert!(cli(&file, &args("--smallest")).is_ok());
+        assert!(cli(&file, &args("--bogus")).is_err());
+        assert!(cli(&file, &args("--smallest extra")).is_err());
+    }
+
+    #[test]
+    fn select_smallest_matches_exhaustive_search() {
+        for bank in small_banks(&[0, 1, 2, 3], 5) {
+            for digits in 0..=bank.len() {
+                let expected = (0..bank.len())
+                    .combinations(digits)
+                    .filter_map(|positions| Input::joltage(&bank, &positions))
+                    .min();
+                let positions = Input::select_smallest(&bank, digits);
+                assert_eq!(
+                    Input::joltage(&bank, &positions),
+                    expected,
+                    "bank {bank:?} with {digits} digits"
+                );
+            }
+        }
+    }
+
+    // A few banks just long enough for part two, often with repeated ratings
+    fn short_banks(rng: &mut Rng) -> String {
+        let max_rating = rng.range(2..=9);
+        (0..rng.range(1..=4))
+            .map(|_| {
+                let bank: String = (0..rng.range(12..=16))
+                    .map(|_| char::from(b'0' + rng.range(1..=max_rating) as u8))
+                    .collect();
+                bank + "\n"
+            })
+            .collect()
+    }
+
+    fn parse_short(contents: &str) -> Option<Input> {
+        let input = Input::parser().parse(contents).into_result().ok()?;
+        let short = input
+            .banks
+            .iter()
+            .all(|bank| (12..=16).contains(&bank.len()));
+        short.then_some(input)
+    }
+
+    fn exhaustive(input: &Input, digits: usize) -> u128 {
+        input
+            .banks
+            .iter()
+            .map(|bank| {
+                (0..bank.len())
+                    .combinations(digits)
+                    .filter_map(|positions| Input::joltage(bank, &positions))
+                    .max()
+                    .unwrap()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn parts_match_exhaustive_search_on_random_banks() {
+        Differential {
+            name: "day03",
+            generate: short_banks,
+            reference: |contents| {
+                let input = parse_short(contents)?;
+                Some((exhaustive(&input, 2), exhaustive(&input, 12)))
+            },
+            optimized: |contents| {
+                let input = parse_short(contents)?;
+                Some((input.part_one().unwrap(), input.part_two().unwrap()))
+            },
+        }
+        .run(1000, &crate::regressions_dir());
+    }
+
+    #[test]
+    fn short_banks_are_an_error() {
+        let input = Input::parse(&"12345\n".into()).unwrap();
+        assert_eq!(input.part_one(), Ok(45));
+        assert_eq!(
+            input.part_two(),
+            Err(Error::TooFewBatteries {
+                bank: 1,
+                digits: 12
+            })
+        );
+    }
+
+    #[test]
+    fn many_digits_work() {
+        let bank: Vec<u64> = (0..60).map(|i| (i * 7 + 3) % 10).collect();
+        let positions = Input::select_largest(&bank, 38);
+        assert_eq!(positions.len(), 38);
+        assert!(Input::joltage(&bank, &positions).is_some());
+        assert_eq!(
+            Input::joltage(&bank, &Input::select_largest(&bank, 40)),
+            None
+        );
+    }
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one().unwrap(), 357);
+        assert_eq!(example.part_two().unwrap(), 3121910778619);
+    }
+
+    util::fuzz_tests! {
+        name: "day03",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
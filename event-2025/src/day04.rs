@@ -0,0 +1,111 @@
+use anyhow::{Context, Result, anyhow};
+use chumsky::prelude::*;
+use util::InputFile;
+use util::automaton::{Automaton, Neighborhood, Outcome};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        Unsettled(outcome: Outcome) =>
+            "rolls never stop being removed, the grid ended in {outcome:?}",
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    grid: Vec<Vec<char>>,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        one_of(".@")
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>()
+            .map(|grid| Self { grid })
+    }
+
+    // A roll is removed once fewer than four of its neighbors are rolls
+    fn removal(cell: &char, neighbors: &[&char]) -> char {
+        let num_adjacent = neighbors.iter().filter(|&&&c| c == '@').count();
+        if *cell == '@' && num_adjacent < 4 {
+            '.'
+        } else {
+            *cell
+        }
+    }
+
+    fn part_one(&self) -> usize {
+        let mut automaton = Automaton::new(self.grid.clone(), Neighborhood::Moore, Self::removal);
+        automaton.step().len()
+    }
+
+    fn part_two(&self) -> Result<usize, Error> {
+        let mut automaton = Automaton::new(self.grid.clone(), Neighborhood::Moore, Self::removal);
+
+        // Rolls are only ever removed, so this should always settle
+        match automaton.run(usize::MAX) {
+            Outcome::FixedPoint { .. } => Ok(automaton.history().iter().map(Vec::len).sum()),
+            outcome => Err(Error::Unsettled(outcome)),
+        }
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(usize, usize)> {
+    let input = Input::parse(input_file)?;
+    let part_two = input.part_two().context("Failed to solve part two")?;
+    Ok((input.part_one(), part_two))
+}
+
+util::aoc_day!(2025, 4, solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const EXAMPLE: &str = indoc! {"
+        ..@@.@@@@.
+        @@@.@.@.@@
+        @@@@@.@.@@
+        @.@@@@..@.
+        @@.@@@@.@@
+        .@@@@@@@.@
+        .@.@.@.@@@
+        @.@@@.@@@@
+        .@@@@@@@@.
+        @.@.@@@.@.
+    "};
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one(), 13);
+        assert_eq!(example.part_two(), Ok(43));
+    }
+
+    util::fuzz_tests! {
+        name: "day04",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
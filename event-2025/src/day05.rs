@@ -0,0 +1,280 @@
+use std::io::{BufRead, Write};
+use std::ops::RangeInclusive;
+
+use anyhow::{Context, Result, anyhow, bail};
+use chumsky::prelude::*;
+use util::InputFile;
+
+// Sorted, disjoint and non-adjacent ranges, so a lookup is a binary search
+#[derive(Debug)]
+struct IntervalIndex {
+    intervals: Vec<RangeInclusive<u64>>,
+}
+
+impl IntervalIndex {
+    fn new(ranges: &[RangeInclusive<u64>]) -> Self {
+        let mut sorted: Vec<_> = ranges.iter().filter(|r| !r.is_empty()).cloned().collect();
+        sorted.sort_by_key(|r| *r.start());
+
+        let mut intervals: Vec<RangeInclusive<u64>> = Vec::new();
+        for range in sorted {
+            match intervals.last_mut() {
+                Some(last) if range.start().saturating_sub(1) <= *last.end() => {
+                    *last = *last.start()..=*last.end().max(range.end());
+                }
+                _ => intervals.push(range),
+            }
+        }
+
+        Self { intervals }
+    }
+
+    fn contains(&self, id: u64) -> bool {
+        let i = self.intervals.partition_point(|r| *r.end() < id);
+        self.intervals.get(i).is_some_and(|r| r.contains(&id))
+    }
+
+    fn query(&self, ids: &[u64]) -> Vec<bool> {
+        ids.iter().map(|&id| self.contains(id)).collect()
+    }
+
+    fn count_contained(&self, ids: &[u64]) -> usize {
+        self.query(ids).into_iter().filter(|&fresh| fresh).count()
+    }
+
+    // A u128 because a single range can cover all 2^64 IDs
+    fn num_ids(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|r| u128::from(r.end() - r.start()) + 1)
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    fresh_id_ranges: Vec<RangeInclusive<u64>>,
+    available_ids: Vec<u64>,
+    index: IntervalIndex,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        let range = util::unsigned::<u64>(10)
+            .then_ignore(just("-"))
+            .then(util::unsigned::<u64>(10))
+            .map(|(start, end)| start..=end);
+
+        let ranges = range
+            .then_ignore(text::newline())
+            .repeated()
+            .at_least(1)
+            .collect::<Vec<_>>();
+
+        let ids = util::unsigned::<u64>(10)
+            .separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>();
+
+        ranges
+            .then_ignore(text::newline())
+            .then(ids)
+            .map(|(fresh_id_ranges, available_ids)| Self {
+                index: IntervalIndex::new(&fresh_id_ranges),
+                fresh_id_ranges,
+                available_ids,
+            })
+    }
+
+    // How many available IDs fall in each range as written, overlaps included
+    fn fresh_per_range(&self) -> Vec<usize> {
+        let mut ids = self.available_ids.clone();
+        ids.sort_unstable();
+
+        self.fresh_id_ranges
+            .iter()
+            .map(|range| {
+                let lo = ids.partition_point(|id| id < range.start());
+                let hi = ids.partition_point(|id| id <= range.end());
+                hi.saturating_sub(lo)
+            })
+            .collect()
+    }
+
+    fn part_one(&self) -> usize {
+        self.index.count_contained(&self.available_ids)
+    }
+
+    fn part_two(&self) -> u128 {
+        self.index.num_ids()
+    }
+}
+
+// Answers one ID per line until the end of the stream
+fn stream(index: &IntervalIndex, reader: impl BufRead, mut out: impl Write) -> Result<()> {
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.context("Failed to read from stdin")?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let id: u64 = line
+            .parse()
+            .with_context(|| format!("Invalid ID '{line}' on line {}", i + 1))?;
+        let status = if index.contains(id) {
+            "fresh"
+        } else {
+            "spoiled"
+        };
+        writeln!(out, "{id} {status}").context("Failed to write to stdout")?;
+    }
+
+    Ok(())
+}
+
+fn solve(input_file: &InputFile) -> Result<(usize, u128)> {
+    let input = Input::parse(input_file)?;
+    Ok((input.part_one(), input.part_two()))
+}
+
+fn cli(input_file: &InputFile, args: &[String]) -> Result<()> {
+    let input = Input::parse(input_file)?;
+    if let Some(extra) = args.get(1) {
+        bail!("Unexpected argument '{extra}'");
+    }
+
+    match args[0].as_str() {
+        "--stream" => stream(
+            &input.index,
+            std::io::stdin().lock(),
+            std::io::stdout().lock(),
+        ),
+        "--per-range" => {
+            for (range, count) in input.fresh_id_ranges.iter().zip(input.fresh_per_range()) {
+                println!("{}-{} {count}", range.start(), range.end());
+            }
+            Ok(())
+        }
+        arg => bail!("Unknown argument '{arg}'"),
+    }
+}
+
+util::aoc_day!(2025, 5, solve, cli);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use std::io::Cursor;
+
+    const EXAMPLE: &str = indoc! {"
+        3-5
+        10-14
+        16-20
+        12-18
+
+        1
+        5
+        8
+        11
+        17
+        32
+    "};
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one(), 3);
+        assert_eq!(example.part_two(), 14);
+        assert_eq!(example.fresh_per_range(), vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn interval_index_works() {
+        let index = IntervalIndex::new(&[
+            10..=14,
+            3..=5,
+            6..=6,
+            16..=20,
+            12..=18,
+            RangeInclusive::new(30, 29),
+        ]);
+        assert_eq!(index.intervals, vec![3..=6, 10..=20]);
+        assert_eq!(index.num_ids(), 15);
+
+        let ids: Vec<u64> = (0..25).collect();
+        let expected: Vec<bool> = ids
+            .iter()
+            .map(|id| (3..=6).contains(id) || (10..=20).contains(id))
+            .collect();
+        assert_eq!(index.query(&ids), expected);
+        assert_eq!(index.count_contained(&ids), 15);
+        assert!(!IntervalIndex::new(&[]).contains(0));
+        assert!(IntervalIndex::new(&[0..=u64::MAX]).contains(u64::MAX));
+    }
+
+    #[test]
+    fn overlapping_ranges_are_counted_separately() {
+        let contents = indoc! {"
+            1-10
+            5-15
+            20-20
+
+            5
+            7
+            12
+            20
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.fresh_per_range(), vec![2, 3, 1]);
+        assert_eq!(input.part_one(), 4);
+    }
+
+    #[test]
+    fn stream_answers_each_id() {
+        let input = Input::parse(&EXAMPLE.into()).unwrap();
+        let mut out = Vec::new();
+        stream(&input.index, Cursor::new("1\n\n 5 \n17\n"), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1 spoiled\n5 fresh\n17 fresh\n"
+        );
+
+        let mut out = Vec::new();
+        let err = stream(&input.index, Cursor::new("5\nfive\n8\n"), &mut out).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid ID 'five' on line 2");
+        assert_eq!(String::from_utf8(out).unwrap(), "5 fresh\n");
+    }
+
+    #[test]
+    fn cli_rejects_other_arguments() {
+        let args = |args: &str| args.split(' ').map(str::to_string).collect::<Vec<_>>();
+        let file = InputFile::from(EXAMPLE);
+        assert!(cli(&file, &args("--per-range")).is_ok());
+        assert!(cli(&file, &args("--bogus")).is_err());
+        assert!(cli(&file, &args("--per-range extra")).is_err());
+    }
+
+    util::fuzz_tests! {
+        name: "day05",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
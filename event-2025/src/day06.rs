@@ -0,0 +1,377 @@
+use anyhow::{Context, Result, anyhow, bail};
+use chumsky::prelude::*;
+use util::{InputFile, Spanned};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        MultipleNumbers { problem: usize, digits: String } =>
+            "problem {problem} has '{digits}' where one number should be",
+        NumberTooLarge { problem: usize, digits: String } =>
+            "number '{digits}' in problem {problem} is too large",
+        NoExactAnswer { problem: usize, numbers: Vec<u64> } =>
+            "problem {problem} has no exact answer for {numbers:?}",
+        TotalOverflow => "total of all problems is too large",
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl TryFrom<char> for Operator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '+' => Ok(Operator::Add),
+            '-' => Ok(Operator::Subtract),
+            '*' => Ok(Operator::Multiply),
+            '/' => Ok(Operator::Divide),
+            _ => Err(anyhow!("Invalid operator character '{}'", value)),
+        }
+    }
+}
+
+impl Operator {
+    // Applies the operator left to right, failing on overflow, a negative result or
+    // inexact division
+    fn fold(self, numbers: &[u64]) -> Option<u64> {
+        let (&first, rest) = numbers.split_first()?;
+        rest.iter().try_fold(first, |acc, &x| match self {
+            Operator::Add => acc.checked_add(x),
+            Operator::Subtract => acc.checked_sub(x),
+            Operator::Multiply => acc.checked_mul(x),
+            Operator::Divide => acc.checked_rem(x).filter(|&r| r == 0).map(|_| acc / x),
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Reading {
+    // Each row holds one number
+    Rows,
+    // Each column holds one number, read top to bottom, and the columns are taken from
+    // left to right
+    ColumnsLeftToRight,
+    // Same as above, but the columns are taken from right to left
+    ColumnsRightToLeft,
+}
+
+#[derive(Debug)]
+struct Problem {
+    operator: Operator,
+    columns: std::ops::Range<usize>,
+}
+
+#[derive(Debug)]
+struct Input {
+    // Padded with spaces so every row has the same width
+    grid: Vec<Vec<char>>,
+    problems: Vec<Problem>,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        let line = |chars: &'static str| {
+            util::spanned(
+                one_of(chars)
+                    .repeated()
+                    .at_least(1)
+                    .to_slice()
+                    .map(|line: &str| line.chars().collect::<Vec<_>>()),
+            )
+        };
+
+        let rows = line("0123456789 ")
+            .separated_by(text::newline())
+            .at_least(1)
+            .collect::<Vec<_>>();
+
+        rows.then_ignore(text::newline())
+            .then(line("+-*/ "))
+            .then_ignore(text::newline().or_not())
+            .try_map(|(rows, operators), _| Self::layout(rows, operators))
+    }
+
+    // Every operator starts a problem, which runs until the blank column right before the
+    // next operator.
+    fn layout<'src>(
+        rows: Vec<Spanned<Vec<char>>>,
+        operators: Spanned<Vec<char>>,
+    ) -> Result<Self, Rich<'src, char>> {
+        let width = rows
+            .iter()
+            .map(|row| row.len())
+            .chain(std::iter::once(operators.len()))
+            .max()
+            .unwrap_or(0);
+        let at =
+            |span: SimpleSpan, col: usize| SimpleSpan::from(span.start + col..span.start + col + 1);
+
+        let starts: Vec<usize> = operators
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c != ' ')
+            .map(|(col, _)| col)
+            .collect();
+
+        if starts.first() != Some(&0) {
+            return Err(Rich::custom(
+                at(operators.span, 0),
+                "operator row should start with an operator",
+            ));
+        }
+
+        let mut problems = Vec::new();
+        for (i, &start) in starts.iter().enumerate() {
+            let end = match starts.get(i + 1) {
+                Some(&next) => next - 1,
+                None => width,
+            };
+
+            if end <= start {
+                return Err(Rich::custom(
+                    at(operators.span, start + 1),
+                    "operators should be separated by at least one column",
+                ));
+            }
+
+            if let Some(row) = rows
+                .iter()
+                .find(|row| row.get(end).is_some_and(|&c| c != ' '))
+            {
+                return Err(Rich::custom(
+                    at(row.span, end),
+                    "column before an operator should be blank in every row",
+                ));
+            }
+
+            let empty = (start..end).find(|&col| {
+                rows.iter()
+                    .all(|row| row.get(col).is_none_or(|&c| c == ' '))
+            });
+            if let Some(col) = empty {
+                return Err(Rich::custom(
+                    at(operators.span, start),
+                    format!("problem has no digits in column {}", col + 1),
+                ));
+            }
+
+            problems.push(Problem {
+                operator: Operator::try_from(operators[start]).unwrap(),
+                columns: start..end,
+            });
+        }
+
+        let grid = rows
+            .into_iter()
+            .map(|row| {
+                let mut row = row.value;
+                row.resize(width, ' ');
+                row
+            })
+            .collect();
+
+        Ok(Self { grid, problems })
+    }
+
+    fn to_number(problem: usize, digits: impl Iterator<Item = char>) -> Result<Option<u64>, Error> {
+        let digits: String = digits.collect();
+        let digits = digits.trim().to_string();
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        if digits.contains(' ') {
+            return Err(Error::MultipleNumbers { problem, digits });
+        }
+        match digits.parse() {
+            Ok(number) => Ok(Some(number)),
+            Err(_) => Err(Error::NumberTooLarge { problem, digits }),
+        }
+    }
+
+    fn numbers(&self, index: usize, reading: Reading) -> Result<Vec<u64>, Error> {
+        let problem = &self.problems[index];
+        let mut numbers = Vec::new();
+        match reading {
+            Reading::Rows => {
+                for row in &self.grid {
+                    let digits = row[problem.columns.clone()].iter().copied();
+                    numbers.extend(Self::to_number(index + 1, digits)?);
+                }
+            }
+            Reading::ColumnsLeftToRight | Reading::ColumnsRightToLeft => {
+                let mut columns: Vec<usize> = problem.columns.clone().collect();
+                if reading == Reading::ColumnsRightToLeft {
+                    columns.reverse();
+                }
+                for col in columns {
+                    let digits = self.grid.iter().map(|row| row[col]);
+                    numbers.extend(Self::to_number(index + 1, digits)?);
+                }
+            }
+        }
+        Ok(numbers)
+    }
+
+    fn solve(&self, reading: Reading) -> Result<u64, Error> {
+        let mut total = 0_u64;
+        for (i, problem) in self.problems.iter().enumerate() {
+            let numbers = self.numbers(i, reading)?;
+            let Some(answer) = problem.operator.fold(&numbers) else {
+                return Err(Error::NoExactAnswer {
+                    problem: i + 1,
+                    numbers,
+                });
+            };
+            total = total.checked_add(answer).ok_or(Error::TotalOverflow)?;
+        }
+        Ok(total)
+    }
+
+    fn part_one(&self) -> Result<u64, Error> {
+        self.solve(Reading::Rows)
+    }
+
+    fn part_two(&self) -> Result<u64, Error> {
+        self.solve(Reading::ColumnsRightToLeft)
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(u64, u64)> {
+    let input = Input::parse(input_file)?;
+    let part_one = input.part_one().context("Failed to solve part one")?;
+    let part_two = input.part_two().context("Failed to solve part two")?;
+    Ok((part_one, part_two))
+}
+
+fn cli(input_file: &InputFile, args: &[String]) -> Result<()> {
+    let input = Input::parse(input_file)?;
+    if let Some(extra) = args.get(1) {
+        bail!("Unexpected argument '{extra}'");
+    }
+
+    match args[0].as_str() {
+        "--left-to-right" => {
+            let answer = input.solve(Reading::ColumnsLeftToRight);
+            println!("{}", answer.context("Failed to solve part two")?);
+        }
+        arg => bail!("Unknown argument '{arg}'"),
+    }
+
+    Ok(())
+}
+
+util::aoc_day!(2025, 6, solve, cli);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const EXAMPLE: &str = indoc! {"
+        123 328  51 64 
+         45 64  387 23 
+          6 98  215 314
+        *   +   *   +  
+    "};
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one().unwrap(), 4277556);
+        assert_eq!(example.part_two().unwrap(), 3263827);
+        // Only the order of subtraction and division depends on the direction
+        let left_to_right = example.solve(Reading::ColumnsLeftToRight).unwrap();
+        assert_eq!(left_to_right, 3263827);
+    }
+
+    #[test]
+    fn cli_rejects_other_arguments() {
+        let args = |args: &str| args.split(' ').map(str::to_string).collect::<Vec<_>>();
+        let file = InputFile::from(EXAMPLE);
+        assert!(cli(&file, &args("--left-to-right")).is_ok());
+        assert!(cli(&file, &args("--bogus")).is_err());
+        assert!(cli(&file, &args("--left-to-right extra")).is_err());
+    }
+
+    #[test]
+    fn zero_columns_work() {
+        let contents = indoc! {"
+            10 5
+            20 5
+            +  *
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one().unwrap(), 55);
+        assert_eq!(input.part_two().unwrap(), 12 + 55);
+    }
+
+    #[test]
+    fn other_operators_work() {
+        let contents = indoc! {"
+            95 93
+            13 31
+            -  / 
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one().unwrap(), 82 + 3);
+        assert_eq!(input.solve(Reading::ColumnsLeftToRight).unwrap(), 38 + 3);
+        // Reading right to left gives 53 - 91 and 31 / 93
+        assert_eq!(
+            input.part_two(),
+            Err(Error::NoExactAnswer {
+                problem: 1,
+                numbers: vec![53, 91]
+            })
+        );
+
+        let input = Input::parse(&"7\n2\n/".into()).unwrap();
+        assert_eq!(
+            input.part_one(),
+            Err(Error::NoExactAnswer {
+                problem: 1,
+                numbers: vec![7, 2]
+            })
+        );
+    }
+
+    #[test]
+    fn misaligned_operators_fail() {
+        // Operator doesn't start at the left edge
+        assert!(Input::parse(&" 1\n +".into()).is_err());
+        // Column before the second operator isn't blank
+        assert!(Input::parse(&"12 3\n+ *".into()).is_err());
+        // Operators next to each other
+        assert!(Input::parse(&"1 2\n+*".into()).is_err());
+        // A problem with an empty column
+        assert!(Input::parse(&"1  2\n+   *".into()).is_err());
+    }
+
+    util::fuzz_tests! {
+        name: "day06",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
@@ -0,0 +1,469 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result, anyhow, bail};
+use chumsky::input::Emitter;
+use chumsky::prelude::*;
+use num_bigint::BigUint;
+use util::{InputFile, Spanned};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        Loop { row: usize, col: usize } => "beam at row {row}, column {col} loops forever",
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Start,
+    Splitter,
+    // Reflects beams like `/`
+    ForwardMirror,
+    // Reflects beams like `\`
+    BackMirror,
+    Absorber,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Cell::Empty),
+            'S' => Ok(Cell::Start),
+            '^' => Ok(Cell::Splitter),
+            '/' => Ok(Cell::ForwardMirror),
+            '\\' => Ok(Cell::BackMirror),
+            '#' => Ok(Cell::Absorber),
+            _ => Err(anyhow!("Invalid cell character '{}'", value)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn reflect(self, cell: Cell) -> Self {
+        match (cell, self) {
+            (Cell::ForwardMirror, Direction::Right) | (Cell::BackMirror, Direction::Left) => {
+                Direction::Up
+            }
+            (Cell::ForwardMirror, Direction::Left) | (Cell::BackMirror, Direction::Right) => {
+                Direction::Down
+            }
+            (Cell::ForwardMirror, Direction::Up) | (Cell::BackMirror, Direction::Down) => {
+                Direction::Right
+            }
+            (Cell::ForwardMirror, Direction::Down) | (Cell::BackMirror, Direction::Up) => {
+                Direction::Left
+            }
+            _ => self,
+        }
+    }
+
+    fn sides(self) -> [Direction; 2] {
+        match self {
+            Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+            Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Beam {
+    pos: (usize, usize),
+    direction: Direction,
+}
+
+// Where a beam goes after passing through a cell. Beams that leave the grid or hit an
+// absorber end their timeline, while beams split off the side of the grid are lost.
+#[derive(Debug, Default)]
+struct Successors {
+    beams: Vec<Beam>,
+    ended: u32,
+}
+
+#[derive(Debug)]
+struct Input {
+    start: (usize, usize),
+    grid: Vec<Vec<Cell>>,
+    width: usize,
+    height: usize,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        let cell = one_of(".^S/\\#").map(Cell::try_from).unwrapped();
+        let line = util::spanned(cell.repeated().at_least(1).collect::<Vec<_>>());
+
+        line.separated_by(text::newline())
+            .allow_trailing()
+            .at_least(1)
+            .collect::<Vec<_>>()
+            .validate(|rows, e, emitter| {
+                Self::validate(&rows, e.span(), emitter);
+                Input::from_grid(rows.into_iter().map(|row| row.value).collect())
+            })
+    }
+
+    // The manifold should be a rectangle with exactly one start
+    fn validate<'src>(
+        rows: &[Spanned<Vec<Cell>>],
+        span: SimpleSpan,
+        emitter: &mut Emitter<Rich<'src, char>>,
+    ) {
+        let width = rows[0].len();
+        for row in &rows[1..] {
+            if row.len() != width {
+                let msg = format!("row is {} cells wide, but the first is {width}", row.len());
+                emitter.emit(Rich::custom(row.span, msg));
+            }
+        }
+
+        let mut starts = rows.iter().flat_map(|row| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &cell)| cell == Cell::Start)
+                .map(|(col, _)| SimpleSpan::from(row.span.start + col..row.span.start + col + 1))
+        });
+        if starts.next().is_none() {
+            emitter.emit(Rich::custom(span, "manifold has no start"));
+        }
+        for start in starts {
+            emitter.emit(Rich::custom(start, "manifold has more than one start"));
+        }
+    }
+
+    fn from_grid(grid: Vec<Vec<Cell>>) -> Self {
+        let start = grid
+            .iter()
+            .enumerate()
+            .find_map(|(row, line)| {
+                let col = line.iter().position(|&c| c == Cell::Start)?;
+                Some((row, col))
+            })
+            .unwrap_or((0, 0));
+
+        Self {
+            start,
+            width: grid[0].len(),
+            height: grid.len(),
+            grid,
+        }
+    }
+
+    fn step(&self, (row, col): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (row, col) = match direction {
+            Direction::Up => (row.checked_sub(1)?, col),
+            Direction::Down => (row + 1, col),
+            Direction::Left => (row, col.checked_sub(1)?),
+            Direction::Right => (row, col + 1),
+        };
+        (row < self.height && col < self.width).then_some((row, col))
+    }
+
+    fn cell(&self, (row, col): (usize, usize)) -> Cell {
+        self.grid[row].get(col).copied().unwrap_or(Cell::Empty)
+    }
+
+    // A splitter sends beams out of both of its sides, still heading the same way
+    fn successors(&self, beam: Beam) -> Successors {
+        let mut successors = Successors::default();
+        let cell = self.cell(beam.pos);
+
+        match cell {
+            Cell::Absorber => successors.ended += 1,
+            Cell::Splitter => {
+                for side in beam.direction.sides() {
+                    if let Some(pos) = self.step(beam.pos, side) {
+                        successors.beams.push(Beam {
+                            pos,
+                            direction: beam.direction,
+                        });
+                    }
+                }
+            }
+            _ => {
+                let direction = beam.direction.reflect(cell);
+                match self.step(beam.pos, direction) {
+                    Some(pos) => successors.beams.push(Beam { pos, direction }),
+                    None => successors.ended += 1,
+                }
+            }
+        }
+
+        successors
+    }
+
+    fn start_beam(&self) -> Beam {
+        Beam {
+            pos: self.start,
+            direction: Direction::Down,
+        }
+    }
+
+    // Every beam state reachable from the start, which is finite even if beams loop
+    fn lit_beams(&self) -> HashSet<Beam> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![self.start_beam()];
+
+        while let Some(beam) = stack.pop() {
+            if visited.insert(beam) {
+                stack.extend(self.successors(beam).beams);
+            }
+        }
+
+        visited
+    }
+
+    fn lit_cells(&self) -> HashSet<(usize, usize)> {
+        self.lit_beams().into_iter().map(|beam| beam.pos).collect()
+    }
+
+    fn splitters_hit(&self) -> HashSet<(usize, usize)> {
+        self.lit_cells()
+            .into_iter()
+            .filter(|&pos| self.cell(pos) == Cell::Splitter)
+            .collect()
+    }
+
+    // Counts the distinct paths from the start with a post-order walk. A path that loops
+    // back onto itself would give infinitely many timelines, so that is an error.
+    fn timelines(&self) -> Result<BigUint, Error> {
+        enum Visit {
+            Enter(Beam),
+            Exit(Beam),
+        }
+
+        let mut counts: HashMap<Beam, BigUint> = HashMap::new();
+        let mut on_path = HashSet::new();
+        let mut stack = vec![Visit::Enter(self.start_beam())];
+
+        while let Some(visit) = stack.pop() {
+            match visit {
+                Visit::Enter(beam) => {
+                    if counts.contains_key(&beam) {
+                        continue;
+                    }
+                    if !on_path.insert(beam) {
+                        return Err(Error::Loop {
+                            row: beam.pos.0 + 1,
+                            col: beam.pos.1 + 1,
+                        });
+                    }
+                    stack.push(Visit::Exit(beam));
+                    stack.extend(self.successors(beam).beams.into_iter().map(Visit::Enter));
+                }
+                Visit::Exit(beam) => {
+                    let successors = self.successors(beam);
+                    let count = successors
+                        .beams
+                        .iter()
+                        .map(|next| &counts[next])
+                        .fold(BigUint::from(successors.ended), |acc, n| acc + n);
+                    counts.insert(beam, count);
+                    on_path.remove(&beam);
+                }
+            }
+        }
+
+        Ok(counts.remove(&self.start_beam()).unwrap())
+    }
+
+    fn overlay(&self) -> String {
+        let lit = self.lit_cells();
+        let mut result = String::new();
+
+        for (row, line) in self.grid.iter().enumerate() {
+            for (col, &cell) in line.iter().enumerate() {
+                result.push(match cell {
+                    Cell::Empty if lit.contains(&(row, col)) => '|',
+                    Cell::Empty => '.',
+                    Cell::Start => 'S',
+                    Cell::Splitter => '^',
+                    Cell::ForwardMirror => '/',
+                    Cell::BackMirror => '\\',
+                    Cell::Absorber => '#',
+                });
+            }
+            result.push('\n');
+        }
+
+        result
+    }
+
+    fn part_one(&self) -> usize {
+        self.splitters_hit().len()
+    }
+
+    fn part_two(&self) -> Result<BigUint, Error> {
+        self.timelines()
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(usize, BigUint)> {
+    let input = Input::parse(input_file)?;
+    let part_two = input.part_two().context("Failed to solve part two")?;
+    Ok((input.part_one(), part_two))
+}
+
+fn cli(input_file: &InputFile, args: &[String]) -> Result<()> {
+    let input = Input::parse(input_file)?;
+
+    if let Some(arg) = args.iter().find(|arg| *arg != "--overlay") {
+        bail!("Unknown argument '{arg}'");
+    }
+    print!("{}", input.overlay());
+
+    println!("{}", input.part_one());
+    println!("{}", input.part_two().context("Failed to solve part two")?);
+
+    Ok(())
+}
+
+util::aoc_day!(2025, 7, solve, cli);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const EXAMPLE: &str = indoc! {"
+        .......S.......
+        ...............
+        .......^.......
+        ...............
+        ......^.^......
+        ...............
+        .....^.^.^.....
+        ...............
+        ....^.^...^....
+        ...............
+        ...^.^...^.^...
+        ...............
+        ..^...^.....^..
+        ...............
+        .^.^.^.^.^...^.
+        ...............
+    "};
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one(), 21);
+        assert_eq!(example.part_two().unwrap(), BigUint::from(40_u32));
+    }
+
+    #[test]
+    fn wide_grid_works() {
+        // Splits near the right edge of a grid that is wider than it is tall
+        let contents = indoc! {"
+            ......S.
+            ......^.
+            ........
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one(), 1);
+        assert_eq!(input.part_two().unwrap(), BigUint::from(2_u32));
+    }
+
+    #[test]
+    fn deep_manifold_works() {
+        // A full triangle of splitters doubles the timelines on every row, which
+        // overflows a u64 well before the bottom
+        let depth: usize = 70;
+        let width = 2 * depth + 1;
+        let mut contents = String::new();
+        for row in 0..=depth {
+            let line: String = (0..width)
+                .map(|col| match row {
+                    0 if col == depth => 'S',
+                    0 => '.',
+                    _ if col.abs_diff(depth) < row && (col + row - depth) % 2 == 1 => '^',
+                    _ => '.',
+                })
+                .collect();
+            contents.push_str(&line);
+            contents.push('\n');
+        }
+
+        let input = Input::parse(&contents.as_str().into()).unwrap();
+        assert_eq!(input.part_one(), depth * (depth + 1) / 2);
+        assert_eq!(input.part_two().unwrap(), BigUint::from(1_u32) << depth);
+    }
+
+    #[test]
+    fn mirrors_and_absorbers_work() {
+        let contents = indoc! {"
+            .S.#
+            .\\./
+            ....
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_two().unwrap(), BigUint::from(1_u32));
+        assert_eq!(input.overlay(), ".S.#\n.\\|/\n....\n");
+    }
+
+    #[test]
+    fn loops_are_errors() {
+        // The beam turns around and comes back down through the start
+        let contents = indoc! {"
+            ./\\
+            .S.
+            ...
+            .\\/
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert!(matches!(input.part_two(), Err(Error::Loop { .. })));
+        assert_eq!(input.part_one(), 0);
+    }
+
+    #[test]
+    fn invalid_manifolds_are_rejected() {
+        let errors = |contents: &str| -> Vec<(String, std::ops::Range<usize>)> {
+            let errs = Input::parser().parse(contents).into_result().unwrap_err();
+            errs.iter()
+                .map(|err| (err.to_string(), err.span().into_range()))
+                .collect()
+        };
+
+        assert_eq!(
+            errors("S.\n...\n"),
+            [("row is 3 cells wide, but the first is 2".to_string(), 3..6)]
+        );
+        assert_eq!(
+            errors(".S.\n.S.\n"),
+            [("manifold has more than one start".to_string(), 5..6)]
+        );
+        assert_eq!(errors("...\n")[0].0, "manifold has no start");
+    }
+
+    util::fuzz_tests! {
+        name: "day07",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
@@ -0,0 +1,418 @@
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+};
+
+use anyhow::{Context, Result, anyhow};
+use chumsky::input::Emitter;
+use chumsky::prelude::*;
+use itertools::Itertools;
+use util::{InputFile, Spanned, pairs};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        NoTilePair => "need at least two red tiles",
+        NonRectilinearEdge { from: (u64, u64), to: (u64, u64) } =>
+            "edge from {from:?} to {to:?} is neither horizontal nor vertical",
+        AreaOverflow => "largest area doesn't fit in a u64",
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    red_tiles: Vec<(u64, u64)>,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        let line = util::spanned(
+            util::unsigned::<u64>(10)
+                .then_ignore(just(','))
+                .then(util::unsigned::<u64>(10)),
+        );
+
+        line.separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>()
+            .validate(|tiles, _, emitter| {
+                Self::validate(&tiles, emitter);
+                Self {
+                    red_tiles: tiles.into_iter().map(|tile| tile.value).collect(),
+                }
+            })
+    }
+
+    // Each tile should share a row or a column with the one before it, and the last one
+    // with the first
+    fn validate<'src>(tiles: &[Spanned<(u64, u64)>], emitter: &mut Emitter<Rich<'src, char>>) {
+        for (a, b) in tiles.iter().circular_tuple_windows() {
+            if a.0 != b.0 && a.1 != b.1 {
+                let msg = format!(
+                    "tile {:?} is in neither the row nor the column of {:?}",
+                    b.value, a.value
+                );
+                emitter.emit(Rich::custom(b.span, msg));
+            }
+        }
+    }
+
+    fn area(a: (u64, u64), b: (u64, u64)) -> u128 {
+        let width = u128::from(a.0.abs_diff(b.0)) + 1;
+        let height = u128::from(a.1.abs_diff(b.1)) + 1;
+        width * height
+    }
+
+    fn part_one(&self) -> Result<u64, Error> {
+        let best = pairs::max_rectangle(&self.red_tiles).ok_or(Error::NoTilePair)?;
+        best.score.try_into().map_err(|_| Error::AreaOverflow)
+    }
+
+    fn flood_fill(grid: &mut [Vec<u8>], x: usize, y: usize, marker: u8) {
+        let mut stack = vec![(x, y)];
+
+        while let Some((x, y)) = stack.pop() {
+            if grid[y][x] != b'.' {
+                continue;
+            }
+
+            grid[y][x] = marker;
+
+            if x > 0 {
+                stack.push((x - 1, y));
+            }
+
+            if x + 1 < grid.len() {
+                stack.push((x + 1, y));
+            }
+
+            if y > 0 {
+                stack.push((x, y - 1));
+            }
+
+            if y + 1 < grid.len() {
+                stack.push((x, y + 1));
+            }
+        }
+    }
+
+    fn part_two(&self) -> Result<u64, Error> {
+        // Step 1: Dedup x and y coords, then sort them. Keep a coordinate for the gap after
+        // each tile too, otherwise two edges one tile apart would end up next to each other
+        // and hide the outside between them.
+        let mut x_coords = HashSet::new();
+        let mut y_coords = HashSet::new();
+        for (x, y) in self.red_tiles.iter().copied() {
+            x_coords.insert(x);
+            y_coords.insert(y);
+            x_coords.extend(x.checked_add(1));
+            y_coords.extend(y.checked_add(1));
+        }
+
+        let x_coords: Vec<_> = x_coords.iter().copied().sorted().collect();
+        let y_coords: Vec<_> = y_coords.iter().copied().sorted().collect();
+
+        // Step 2: Create outline of red and green tiles. The first and last rows and columns
+        // stay empty so that the outside region stays connected for flood fill.
+        let n = cmp::max(x_coords.len(), y_coords.len()) + 2;
+        let mut grid = vec![vec![b'.'; n]; n];
+
+        let x_translate: HashMap<_, _> = x_coords
+            .iter()
+            .copied()
+            .enumerate()
+            .map(|(i, x)| (x, i + 1))
+            .collect();
+        let y_translate: HashMap<_, _> = y_coords
+            .iter()
+            .copied()
+            .enumerate()
+            .map(|(i, y)| (y, i + 1))
+            .collect();
+
+        let translate = |(x, y)| (*x_translate.get(&x).unwrap(), *y_translate.get(&y).unwrap());
+
+        for (a, b) in self.red_tiles.iter().copied().circular_tuple_windows() {
+            let (x1, y1) = translate(a);
+            let (x2, y2) = translate(b);
+
+            if x1 == x2 {
+                // Vertical line
+                let min_y = cmp::min(y1, y2);
+                let max_y = cmp::max(y1, y2);
+
+                for row in &mut grid[min_y..=max_y] {
+                    row[x1] = b'#';
+                }
+            } else if y1 == y2 {
+                // Horizontal line
+                let min_x = cmp::min(x1, x2);
+                let max_x = cmp::max(x1, x2);
+
+                grid[y1][min_x..=max_x].fill(b'#');
+            } else {
+                return Err(Error::NonRectilinearEdge { from: a, to: b });
+            }
+        }
+
+        // Step 3: Floodfill
+
+        // Fill the outside
+        Self::flood_fill(&mut grid, 0, 0, b'~');
+
+        // Fill the inside
+        for y in 0..n {
+            for x in 0..n {
+                if grid[y][x] == b'.' {
+                    Self::flood_fill(&mut grid, x, y, b'#');
+                }
+            }
+        }
+
+        // Step 4: Count the cells outside the loop with 2D prefix sums, so checking a
+        // rectangle is constant time. Pairs are tried from the largest area down, so most
+        // of them are never checked at all.
+        let mut outside = vec![vec![0_u32; n + 1]; n + 1];
+        for y in 0..n {
+            for x in 0..n {
+                outside[y + 1][x + 1] =
+                    u32::from(grid[y][x] != b'#') + outside[y][x + 1] + outside[y + 1][x]
+                        - outside[y][x];
+            }
+        }
+
+        let is_inside = |a, b| {
+            let (x1, y1) = translate(a);
+            let (x2, y2) = translate(b);
+
+            let (min_x, max_x) = (cmp::min(x1, x2), cmp::max(x1, x2) + 1);
+            let (min_y, max_y) = (cmp::min(y1, y2), cmp::max(y1, y2) + 1);
+
+            outside[max_y][max_x] + outside[min_y][min_x]
+                == outside[min_y][max_x] + outside[max_y][min_x]
+        };
+
+        let best = pairs::best_pair_parallel(
+            &self.red_tiles,
+            |&a, &b| Self::area(a, b),
+            |&a, &b| is_inside(a, b).then(|| Self::area(a, b)),
+        )
+        .ok_or(Error::NoTilePair)?;
+        best.score.try_into().map_err(|_| Error::AreaOverflow)
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(u64, u64)> {
+    let input = Input::parse(input_file)?;
+    let part_one = input.part_one().context("Failed to solve part one")?;
+    let part_two = input.part_two().context("Failed to solve part two")?;
+    Ok((part_one, part_two))
+}
+
+util::aoc_day!(2025, 9, solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use util::differential::Differential;
+    use util::rng::Rng;
+
+    const EXAMPLE: &str = indoc! {"
+        7,1
+        11,1
+        11,7
+        9,7
+        9,5
+        2,5
+        2,3
+        7,3
+    "};
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one().unwrap(), 50);
+        assert_eq!(example.part_two().unwrap(), 24);
+    }
+
+    // An x-monotone loop of up to six bars side by side, each overlapping the next. It's
+    // traced along the tops from left to right and back along the bottoms.
+    fn small_loop(rng: &mut Rng) -> String {
+        let bars = rng.range(1..=6) as usize;
+        let mut xs = vec![rng.range(1..=4)];
+        for i in 0..bars {
+            xs.push(xs[i] + rng.range(1..=4));
+        }
+
+        let mut bars_y: Vec<(u64, u64)> = Vec::new();
+        while bars_y.len() < bars {
+            let bottom = rng.range(1..=15);
+            let top = rng.range(bottom + 2..=20);
+            let fits = bars_y
+                .last()
+                .is_none_or(|&(b, t)| bottom < t && b < top && bottom != b && top != t);
+            if fits {
+                bars_y.push((bottom, top));
+            }
+        }
+
+        let mut tiles = Vec::new();
+        for (i, &(_, top)) in bars_y.iter().enumerate() {
+            tiles.push((xs[i], top));
+            tiles.push((xs[i + 1], top));
+        }
+        for (i, &(bottom, _)) in bars_y.iter().enumerate().rev() {
+            tiles.push((xs[i + 1], bottom));
+            tiles.push((xs[i], bottom));
+        }
+        tiles.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+    }
+
+    fn parse_loop(contents: &str) -> Option<Input> {
+        let input = Input::parser().parse(contents).into_result().ok()?;
+        let small = input
+            .red_tiles
+            .iter()
+            .all(|&(x, y)| (1..=40).contains(&x) && (1..=40).contains(&y));
+        let axis_aligned = input
+            .red_tiles
+            .iter()
+            .circular_tuple_windows()
+            .all(|(a, b)| (a.0 == b.0) != (a.1 == b.1));
+        (input.red_tiles.len() >= 4 && small && axis_aligned).then_some(input)
+    }
+
+    // Both parts by drawing the loop on the full grid and checking every pair of tiles
+    fn rasterized(input: &Input) -> (u64, u64) {
+        let n = input
+            .red_tiles
+            .iter()
+            .map(|&(x, y)| x.max(y) as usize + 2)
+            .max()
+            .unwrap();
+        let mut grid = vec![vec![b'.'; n]; n];
+        for (a, b) in input.red_tiles.iter().copied().circular_tuple_windows() {
+            for x in a.0.min(b.0)..=a.0.max(b.0) {
+                for y in a.1.min(b.1)..=a.1.max(b.1) {
+                    grid[y as usize][x as usize] = b'#';
+                }
+            }
+        }
+        Input::flood_fill(&mut grid, 0, 0, b'~');
+
+        let mut best = (0, 0);
+        for (&a, &b) in input.red_tiles.iter().tuple_combinations() {
+            let area = u64::try_from(Input::area(a, b)).unwrap();
+            best.0 = best.0.max(area);
+
+            let inside = (a.1.min(b.1)..=a.1.max(b.1)).all(|y| {
+                (a.0.min(b.0)..=a.0.max(b.0)).all(|x| grid[y as usize][x as usize] != b'~')
+            });
+            if inside {
+                best.1 = best.1.max(area);
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn parts_match_rasterized_on_random_loops() {
+        Differential {
+            name: "day09",
+            generate: small_loop,
+            reference: |contents| Some(rasterized(&parse_loop(contents)?)),
+            optimized: |contents| {
+                let input = parse_loop(contents)?;
+                Some((input.part_one().unwrap(), input.part_two().unwrap()))
+            },
+        }
+        .run(1000, &crate::regressions_dir());
+    }
+
+    #[test]
+    fn edges_one_tile_apart_work() {
+        // The notch between x = 14 and x = 16 is outside, even though no tile is there
+        let contents = indoc! {"
+            2,1
+            2,14
+            9,14
+            9,11
+            14,11
+            14,17
+            16,17
+            16,13
+            17,13
+            17,20
+            25,20
+            25,1
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_two().unwrap(), 204);
+    }
+
+    #[test]
+    fn diagonal_edges_are_rejected() {
+        let contents = "1,1\n1,4\n3,4\n3,1\n2,2\n";
+        let errs = Input::parser().parse(contents).into_result().unwrap_err();
+        let errs: Vec<_> = errs
+            .iter()
+            .map(|err| (err.to_string(), err.span().into_range()))
+            .collect();
+        assert_eq!(
+            errs,
+            [
+                (
+                    "tile (2, 2) is in neither the row nor the column of (3, 1)".to_string(),
+                    16..19
+                ),
+                (
+                    "tile (1, 1) is in neither the row nor the column of (2, 2)".to_string(),
+                    0..3
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_loops_are_errors() {
+        // Parsing rejects these, so this checks the parts don't rely on it
+        let input = Input {
+            red_tiles: vec![(1, 1), (3, 3)],
+        };
+        assert_eq!(input.part_one(), Ok(9));
+        assert_eq!(
+            input.part_two(),
+            Err(Error::NonRectilinearEdge {
+                from: (1, 1),
+                to: (3, 3)
+            })
+        );
+
+        let input = Input::parse(&"5,5\n".into()).unwrap();
+        assert_eq!(input.part_one(), Err(Error::NoTilePair));
+        assert_eq!(input.part_two(), Err(Error::NoTilePair));
+    }
+
+    util::fuzz_tests! {
+        name: "day09",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
@@ -0,0 +1,520 @@
+use anyhow::{Context, Result, anyhow};
+use chumsky::prelude::*;
+#[cfg(feature = "highs")]
+use good_lp::{Expression, Solution, SolverModel, constraint, highs, variables};
+use util::gf2::{self, BitVec, Matrix};
+use util::ilp;
+use util::{InputFile, Spanned, parallel};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        LightsUnreachable(machine: usize) => "machine {machine} can't reach its lights",
+        JoltagesUnreachable(machine: usize) => "machine {machine} can't reach its joltages",
+        LightsTooHard { machine: usize, cause: gf2::Error } => "machine {machine}: {cause}",
+        PressesOverflow => "total presses overflow",
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Machine {
+    num_lights: usize,
+    lights: u32,
+    buttons: Vec<u32>,
+    requirements: Vec<u32>,
+}
+
+impl Machine {
+    fn new(lights_str: &str, buttons: &[Vec<u32>], requirements: Vec<u32>) -> Self {
+        let lights =
+            lights_str.char_indices().fold(
+                0,
+                |mask, (i, c)| if c == '#' { mask | (1 << i) } else { mask },
+            );
+
+        let buttons = buttons
+            .iter()
+            .map(|button| button.iter().fold(0, |mask, i| mask | (1 << i)))
+            .collect::<Vec<_>>();
+
+        Self {
+            num_lights: lights_str.len(),
+            lights,
+            buttons,
+            requirements,
+        }
+    }
+
+    fn fewest_presses(&self) -> Result<Option<u32>, gf2::Error> {
+        // Pressing a button twice is the same as doing nothing, so the presses form
+        // a linear system over GF(2) with a row per light and a column per button.
+        // The fewest presses is the solution with the fewest ones.
+        let mut matrix = Matrix::zeros(self.num_lights, self.buttons.len());
+        for (col, button) in self.buttons.iter().enumerate() {
+            for row in 0..self.num_lights {
+                matrix.set(row, col, button & (1 << row) != 0);
+            }
+        }
+
+        let target: BitVec = (0..self.num_lights)
+            .map(|row| self.lights & (1 << row) != 0)
+            .collect();
+
+        let presses = matrix.min_weight_solution(&target)?;
+        Ok(presses.and_then(|presses| presses.count_ones().try_into().ok()))
+    }
+
+    fn fewest_presses_joltage(&self) -> Option<u32> {
+        #[cfg(feature = "highs")]
+        if let Some(presses) = self.fewest_presses_joltage_highs() {
+            return Some(presses);
+        }
+
+        self.fewest_presses_joltage_exact()
+    }
+
+    #[cfg(feature = "highs")]
+    fn fewest_presses_joltage_highs(&self) -> Option<u32> {
+        variables! {vars: 0 <= x[self.buttons.len()] (integer); }
+
+        let objective = (0..vars.len()).map(|i| x[i]).sum::<Expression>();
+
+        let mut problem = vars.minimise(objective.clone()).using(highs);
+
+        for (i, required_value) in self.requirements.iter().enumerate() {
+            let idxs = self
+                .buttons
+                .iter()
+                .enumerate()
+                .filter(|&(_, button)| button & (1 << i) != 0)
+                .map(|(i, _)| i);
+
+            problem.add_constraint(constraint!(
+                idxs.map(|i| x[i]).sum::<Expression>() == *required_value
+            ));
+        }
+
+        let solution = problem.solve().ok()?;
+
+        // The solver works in floating point, so round instead of truncating
+        Some(solution.eval(objective).round() as u32)
+    }
+
+    fn fewest_presses_joltage_exact(&self) -> Option<u32> {
+        let matrix: Vec<Vec<u64>> = (0..self.requirements.len())
+            .map(|i| {
+                self.buttons
+                    .iter()
+                    .map(|button| u64::from(button & (1 << i) != 0))
+                    .collect()
+            })
+            .collect();
+        let requirements: Vec<u64> = self.requirements.iter().copied().map(u64::from).collect();
+
+        let presses = ilp::min_sum_solution(&matrix, &requirements)?;
+        presses.iter().sum::<u64>().try_into().ok()
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    machines: Vec<Spanned<Machine>>,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        let lights = just('[')
+            .ignore_then(one_of(".#").repeated().at_least(1).to_slice())
+            .then_ignore(just(']'));
+
+        let button = just('(')
+            .ignore_then(
+                util::spanned(util::unsigned::<u32>(10))
+                    .separated_by(just(','))
+                    .collect::<Vec<_>>(),
+            )
+            .then_ignore(just(')'));
+
+        let requirements = just('{')
+            .ignore_then(
+                util::unsigned::<u32>(10)
+                    .separated_by(just(','))
+                    .collect::<Vec<_>>(),
+            )
+            .then_ignore(just('}'));
+
+        // Lights are kept as bits of a u32
+        let line = util::spanned(lights)
+            .padded()
+            .then(button.padded().repeated().at_least(1).collect::<Vec<_>>())
+            .then(util::spanned(requirements))
+            .try_map(|((lights, buttons), requirements), _| {
+                let num_lights = lights.len();
+                if num_lights > 32 {
+                    let msg = "Machines have at most 32 lights";
+                    return Err(Rich::custom(lights.span, msg));
+                }
+                if let Some(i) = buttons
+                    .iter()
+                    .flatten()
+                    .find(|i| i.value as usize >= num_lights)
+                {
+                    let msg = format!(
+                        "Button wires light {}, but there are only {num_lights}",
+                        i.value
+                    );
+                    return Err(Rich::custom(i.span, msg));
+                }
+                if requirements.len() != num_lights {
+                    let msg = format!(
+                        "{} joltage requirements for {num_lights} lights",
+                        requirements.len()
+                    );
+                    return Err(Rich::custom(requirements.span, msg));
+                }
+                let buttons: Vec<Vec<u32>> = buttons
+                    .into_iter()
+                    .map(|button| button.into_iter().map(|i| i.value).collect())
+                    .collect();
+                Ok(Machine::new(lights.value, &buttons, requirements.value))
+            });
+
+        util::spanned(line)
+            .separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>()
+            .map(|lines| Self { machines: lines })
+    }
+
+    // `presses` gets each machine along with its number, for its errors
+    fn total_presses(
+        &self,
+        presses: fn(usize, &Machine) -> Result<u32, Error>,
+    ) -> Result<u32, Error> {
+        let presses = parallel::map(&self.machines, |i, machine| presses(i + 1, machine));
+
+        // Added up in order, so the first machine to fail is the one reported
+        presses.into_iter().try_fold(0_u32, |total, presses| {
+            total.checked_add(presses?).ok_or(Error::PressesOverflow)
+        })
+    }
+
+    fn part_one(&self) -> Result<u32, Error> {
+        self.total_presses(|number, machine| match machine.fewest_presses() {
+            Ok(presses) => presses.ok_or(Error::LightsUnreachable(number)),
+            Err(cause) => Err(Error::LightsTooHard {
+                machine: number,
+                cause,
+            }),
+        })
+    }
+
+    fn part_two(&self) -> Result<u32, Error> {
+        self.total_presses(|number, machine| {
+            machine
+                .fewest_presses_joltage()
+                .ok_or(Error::JoltagesUnreachable(number))
+        })
+    }
+
+    // Points at the machine a part failed on, if it failed on one
+    fn report<T>(&self, file: &InputFile, answer: &Result<T, Error>) {
+        if let Err(
+            err @ (Error::LightsUnreachable(i)
+            | Error::JoltagesUnreachable(i)
+            | Error::LightsTooHard { machine: i, .. }),
+        ) = answer
+        {
+            file.report(self.machines[*i - 1].span, err);
+        }
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(u32, u32)> {
+    let input = Input::parse(input_file)?;
+    let part_one = input.part_one();
+    input.report(input_file, &part_one);
+    let part_two = input.part_two();
+    input.report(input_file, &part_two);
+    Ok((
+        part_one.context("Failed to solve part one")?,
+        part_two.context("Failed to solve part two")?,
+    ))
+}
+
+util::aoc_day!(2025, 10, solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use itertools::Itertools;
+    use util::differential::Differential;
+    use util::rng::Rng;
+
+    const EXAMPLE: &str = indoc! {"
+        [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+        [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+        [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+    "};
+
+    #[test]
+    fn fewest_presses_works() {
+        let contents = indoc! {"
+            [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+            [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+            [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+        "};
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.machines[0].fewest_presses(), Ok(Some(2)));
+        assert_eq!(example.machines[1].fewest_presses(), Ok(Some(3)));
+        assert_eq!(example.machines[2].fewest_presses(), Ok(Some(2)));
+    }
+
+    #[test]
+    fn fewest_presses_many_buttons_works() {
+        let buttons = "(0,1) (0,2) (3) (1,3) ".repeat(50);
+        let contents = format!("[.##.] {buttons}{{1,1,1,1}}");
+        let machine = &Input::parse(&contents.as_str().into()).unwrap().machines[0];
+        assert_eq!(machine.buttons.len(), 200);
+        assert_eq!(machine.fewest_presses(), Ok(Some(2)));
+    }
+
+    #[test]
+    fn fewest_presses_joltage_works() {
+        let contents = indoc! {"
+            [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+            [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+            [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+        "};
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.machines[0].fewest_presses_joltage(), Some(10));
+        assert_eq!(example.machines[1].fewest_presses_joltage(), Some(12));
+        assert_eq!(example.machines[2].fewest_presses_joltage(), Some(11));
+    }
+
+    #[test]
+    fn fewest_presses_joltage_exact_works() {
+        let contents = indoc! {"
+            [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+            [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+            [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+        "};
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.machines[0].fewest_presses_joltage_exact(), Some(10));
+        assert_eq!(example.machines[1].fewest_presses_joltage_exact(), Some(12));
+        assert_eq!(example.machines[2].fewest_presses_joltage_exact(), Some(11));
+    }
+
+    #[cfg(feature = "highs")]
+    #[test]
+    fn fewest_presses_joltage_exact_matches_highs() {
+        let contents = indoc! {"
+            [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+            [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+            [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+            [#..#] (0,1) (1,2) (2,3) (0,3) (0,2) {9,14,21,16}
+            [##.] (0) (1) (0,1,2) {20,7,4}
+            [.#] (0) {3,1}
+        "};
+        let example = Input::parse(&contents.into()).unwrap();
+        for machine in &example.machines {
+            assert_eq!(
+                machine.fewest_presses_joltage_exact(),
+                machine.fewest_presses_joltage_highs()
+            );
+        }
+    }
+
+    // Machines with a handful of lights and buttons, set up by a few presses each
+    fn small_machines(rng: &mut Rng) -> String {
+        let machines: String = (0..rng.range(1..=3))
+            .map(|_| {
+                let num_lights = rng.range(1..=4) as usize;
+                let buttons: Vec<Vec<usize>> = (0..rng.range(1..=5))
+                    .map(|_| {
+                        let mut button: Vec<usize> =
+                            (0..num_lights).filter(|_| rng.chance(0.5)).collect();
+                        if button.is_empty() {
+                            button.push(rng.index(num_lights));
+                        }
+                        button
+                    })
+                    .collect();
+
+                let mut lights = vec!['.'; num_lights];
+                let mut joltages = vec![0; num_lights];
+                for button in &buttons {
+                    let presses = rng.range(0..=3);
+                    for &i in button {
+                        if presses % 2 == 1 {
+                            lights[i] = if lights[i] == '.' { '#' } else { '.' };
+                        }
+                        joltages[i] += presses;
+                    }
+                }
+
+                let buttons = buttons
+                    .iter()
+                    .map(|button| format!("({})", button.iter().join(",")));
+                format!(
+                    "[{}] {} {{{}}}\n",
+                    lights.iter().collect::<String>(),
+                    buttons.format(" "),
+                    joltages.iter().join(",")
+                )
+            })
+            .collect();
+        machines
+    }
+
+    fn parse_small(contents: &str) -> Option<Input> {
+        let input = Input::parser().parse(contents).into_result().ok()?;
+        let small = input.machines.iter().all(|machine| {
+            machine.buttons.len() <= 8
+                && machine
+                    .buttons
+                    .iter()
+                    .all(|&b| b >> machine.num_lights == 0)
+                && machine.requirements.len() == machine.num_lights
+                && machine.requirements.iter().all(|&r| r <= 20)
+        });
+        small.then_some(input)
+    }
+
+    // Tries every subset of buttons
+    fn fewest_presses_subsets(machine: &Machine) -> Option<u32> {
+        (0_u32..1 << machine.buttons.len())
+            .filter(|subset| {
+                let lights = (0..machine.buttons.len())
+                    .filter(|i| subset & (1 << i) != 0)
+                    .fold(0, |lights, i| lights ^ machine.buttons[i]);
+                lights == machine.lights
+            })
+            .map(u32::count_ones)
+            .min()
+    }
+
+    // Tries every number of presses for each button in turn, never going over a joltage
+    fn fewest_presses_joltage_search(machine: &Machine) -> Option<u32> {
+        fn search(buttons: &[u32], remaining: &mut [u32]) -> Option<u32> {
+            let Some((&button, rest)) = buttons.split_first() else {
+                return remaining.iter().all(|&r| r == 0).then_some(0);
+            };
+            let lights: Vec<usize> = (0..remaining.len())
+                .filter(|i| button & (1 << i) != 0)
+                .collect();
+            let most = lights.iter().map(|&i| remaining[i]).min().unwrap_or(0);
+
+            let mut best = None;
+            for presses in 0..=most {
+                for &i in &lights {
+                    remaining[i] -= presses;
+                }
+                if let Some(more) = search(rest, remaining) {
+                    best = Some(best.map_or(presses + more, |b: u32| b.min(presses + more)));
+                }
+                for &i in &lights {
+                    remaining[i] += presses;
+                }
+            }
+            best
+        }
+
+        search(&machine.buttons, &mut machine.requirements.clone())
+    }
+
+    #[test]
+    fn fewest_presses_match_brute_force_on_random_machines() {
+        Differential {
+            name: "day10",
+            generate: small_machines,
+            reference: |contents| {
+                let input = parse_small(contents)?;
+                let presses = input.machines.iter().map(|machine| {
+                    (
+                        fewest_presses_subsets(machine),
+                        fewest_presses_joltage_search(machine),
+                    )
+                });
+                Some(presses.collect::<Vec<_>>())
+            },
+            optimized: |contents| {
+                let input = parse_small(contents)?;
+                let presses = input.machines.iter().map(|machine| {
+                    (
+                        machine.fewest_presses().unwrap(),
+                        machine.fewest_presses_joltage(),
+                    )
+                });
+                Some(presses.collect::<Vec<_>>())
+            },
+        }
+        .run(1000, &crate::regressions_dir());
+    }
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        dbg!(&example);
+        assert_eq!(example.part_one().unwrap(), 7);
+        assert_eq!(example.part_two().unwrap(), 33);
+    }
+
+    #[test]
+    fn invalid_machines_are_rejected() {
+        let error = |contents: &str| {
+            let errs = Input::parser().parse(contents).into_result().unwrap_err();
+            (errs[0].to_string(), errs[0].span().into_range())
+        };
+
+        assert_eq!(
+            error("[#.] (0) (1,2) {1,0}\n"),
+            (
+                "Button wires light 2, but there are only 2".to_string(),
+                12..13
+            )
+        );
+        assert_eq!(
+            error("[#.] (0) {1,0,3}\n"),
+            ("3 joltage requirements for 2 lights".to_string(), 9..16)
+        );
+    }
+
+    #[test]
+    fn unreachable_machines_are_errors() {
+        let contents = indoc! {"
+            [#.] (0) {1,0}
+            [.#] (0) {1,1}
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one(), Err(Error::LightsUnreachable(2)));
+        assert_eq!(input.part_two(), Err(Error::JoltagesUnreachable(2)));
+
+        // Errors are reported at the machine they're about
+        let span = input.machines[1].span.into_range();
+        assert_eq!(&contents[span], "[.#] (0) {1,1}");
+    }
+
+    util::fuzz_tests! {
+        name: "day10",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
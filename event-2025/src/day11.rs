@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result, anyhow};
+use chumsky::input::Emitter;
+use chumsky::prelude::*;
+use util::{InputFile, Spanned};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        UnknownDevice(device: String) => "device '{device}' is not defined",
+        Loop(device: String) => "device '{device}' is part of a loop",
+        PathsOverflow => "number of paths overflows",
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    lines: HashMap<String, Vec<String>>,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        let name = one_of('a'..='z')
+            .repeated()
+            .at_least(1)
+            .to_slice()
+            .map(ToString::to_string);
+        let line = util::spanned(name.clone())
+            .then_ignore(just(':').padded())
+            .then(
+                util::spanned(name)
+                    .separated_by(just(' ').repeated().at_least(1))
+                    .collect::<Vec<_>>(),
+            );
+        line.separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>()
+            .validate(|lines, _, emitter| {
+                Self::validate(&lines, emitter);
+                let lines = lines.into_iter().map(|(device, outputs)| {
+                    let outputs = outputs.into_iter().map(|name| name.value).collect();
+                    (device.value, outputs)
+                });
+                Self {
+                    lines: lines.collect(),
+                }
+            })
+    }
+
+    // Every device should be defined once, and every output other than `out` should be a
+    // defined device
+    fn validate<'src>(
+        lines: &[(Spanned<String>, Vec<Spanned<String>>)],
+        emitter: &mut Emitter<Rich<'src, char>>,
+    ) {
+        let mut defined = HashSet::new();
+        for (device, _) in lines {
+            if !defined.insert(device.as_str()) {
+                let msg = format!("device '{}' is defined more than once", device.value);
+                emitter.emit(Rich::custom(device.span, msg));
+            }
+        }
+
+        for name in lines.iter().flat_map(|(_, outputs)| outputs) {
+            if name.value != "out" && !defined.contains(name.as_str()) {
+                let msg = format!("device '{}' is not defined", name.value);
+                emitter.emit(Rich::custom(name.span, msg));
+            }
+        }
+    }
+
+    fn outputs(&self, device: &str) -> Result<&[String], Error> {
+        self.lines
+            .get(device)
+            .map(Vec::as_slice)
+            .ok_or_else(|| Error::UnknownDevice(device.to_string()))
+    }
+
+    // Devices on the current path are memoized as `None`, so reaching one again means the
+    // path loops and there are infinitely many of them
+    fn search<'a>(
+        &'a self,
+        curr: &'a str,
+        memo: &mut HashMap<&'a str, Option<usize>>,
+    ) -> Result<usize, Error> {
+        if curr == "out" {
+            return Ok(1);
+        }
+
+        match memo.get(curr) {
+            Some(Some(val)) => return Ok(*val),
+            Some(None) => return Err(Error::Loop(curr.to_string())),
+            None => {}
+        }
+        memo.insert(curr, None);
+
+        let mut total = 0_usize;
+        for s in self.outputs(curr)? {
+            total = total
+                .checked_add(self.search(s, memo)?)
+                .ok_or(Error::PathsOverflow)?;
+        }
+
+        memo.insert(curr, Some(total));
+
+        Ok(total)
+    }
+
+    fn part_one(&self) -> Result<usize, Error> {
+        let mut memo = HashMap::new();
+        self.search("you", &mut memo)
+    }
+
+    fn search2<'a>(
+        &'a self,
+        curr: &'a str,
+        seen_fft: bool,
+        seen_dac: bool,
+        memo: &mut HashMap<(&'a str, bool, bool), Option<usize>>,
+    ) -> Result<usize, Error> {
+        if curr == "out" {
+            return Ok(usize::from(seen_fft && seen_dac));
+        }
+
+        match memo.get(&(curr, seen_fft, seen_dac)) {
+            Some(Some(val)) => return Ok(*val),
+            Some(None) => return Err(Error::Loop(curr.to_string())),
+            None => {}
+        }
+        memo.insert((curr, seen_fft, seen_dac), None);
+
+        let mut total = 0_usize;
+        for s in self.outputs(curr)? {
+            let paths = self.search2(
+                s,
+                seen_fft || (curr == "fft"),
+                seen_dac || (curr == "dac"),
+                memo,
+            )?;
+            total = total.checked_add(paths).ok_or(Error::PathsOverflow)?;
+        }
+
+        memo.insert((curr, seen_fft, seen_dac), Some(total));
+
+        Ok(total)
+    }
+
+    fn part_two(&self) -> Result<usize, Error> {
+        let mut memo = HashMap::new();
+        self.search2("svr", false, false, &mut memo)
+    }
+}
+
+fn solve(input_file: &InputFile) -> Result<(usize, usize)> {
+    let input = Input::parse(input_file)?;
+    let part_one = input.part_one().context("Failed to solve part one")?;
+    let part_two = input.part_two().context("Failed to solve part two")?;
+    Ok((part_one, part_two))
+}
+
+util::aoc_day!(2025, 11, solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const EXAMPLE: &str = indoc! {"
+        aaa: you hhh
+        you: bbb ccc
+        bbb: ddd eee
+        ccc: ddd eee fff
+        ddd: ggg
+        eee: out
+        fff: out
+        ggg: out
+        hhh: ccc fff iii
+        iii: out
+    "};
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one().unwrap(), 5);
+
+        let contents = indoc! {"
+            svr: aaa bbb
+            aaa: fft
+            fft: ccc
+            bbb: tty
+            tty: ccc
+            ccc: ddd eee
+            ddd: hub
+            hub: fff
+            eee: dac
+            dac: fff
+            fff: ggg hhh
+            ggg: out
+            hhh: out
+        "};
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_two().unwrap(), 2);
+    }
+
+    #[test]
+    fn invalid_graphs_are_rejected() {
+        let contents = indoc! {"
+            you: aaa out
+            aaa: xyz
+            you: out
+        "};
+        let errs = Input::parser().parse(contents).into_result().unwrap_err();
+        let errs: Vec<_> = errs
+            .iter()
+            .map(|err| (err.to_string(), err.span().into_range()))
+            .collect();
+        assert_eq!(
+            errs,
+            [
+                ("device 'you' is defined more than once".to_string(), 22..25),
+                ("device 'xyz' is not defined".to_string(), 18..21),
+            ]
+        );
+    }
+
+    #[test]
+    fn bad_graphs_are_errors() {
+        let contents = indoc! {"
+            you: aaa out
+            aaa: out
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one(), Ok(2));
+        assert_eq!(
+            input.part_two(),
+            Err(Error::UnknownDevice("svr".to_string()))
+        );
+
+        let contents = indoc! {"
+            you: aaa
+            aaa: bbb out
+            bbb: aaa
+        "};
+        let input = Input::parse(&contents.into()).unwrap();
+        assert_eq!(input.part_one(), Err(Error::Loop("aaa".to_string())));
+    }
+
+    util::fuzz_tests! {
+        name: "day11",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+            let _ = input.part_two();
+        },
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::{Context, Result, anyhow};
+use chumsky::input::Emitter;
+use chumsky::prelude::*;
+use util::polyomino::{self, Packing, Polyomino};
+use util::{InputFile, Spanned, parallel};
+
+util::error_enum! {
+    #[derive(Debug, PartialEq, Eq)]
+    enum Error {
+        QuantityMismatch { region: usize, quantities: usize, shapes: usize } =>
+            "region {region} lists {quantities} quantities for {shapes} shapes",
+    }
+}
+
+#[derive(Debug)]
+struct Input {
+    shapes: Vec<Polyomino>,
+    regions: Vec<(usize, usize, Vec<usize>)>,
+}
+
+impl Input {
+    fn parse(file: &InputFile) -> Result<Self> {
+        Self::parser()
+            .parse(&file.contents)
+            .into_result()
+            .map_err(|errs| {
+                file.print_diagnostics(errs);
+                anyhow!("Failed to parse input file '{}'", file.path.display())
+            })
+    }
+
+    fn parser<'src>() -> impl Parser<'src, &'src str, Self, extra::Err<Rich<'src, char>>> {
+        let shape = one_of(".#")
+            .repeated()
+            .exactly(3)
+            .to_slice()
+            .map(String::from)
+            .separated_by(text::newline())
+            .collect_exactly::<[_; 3]>()
+            .map(|rows| Polyomino::from_rows(&rows));
+
+        let shapes = util::spanned(util::unsigned::<usize>(10))
+            .then_ignore(just(':').then(text::newline()))
+            .then(shape)
+            .separated_by(text::newline().repeated().exactly(2))
+            .allow_trailing()
+            .collect::<Vec<_>>();
+
+        let region_dim = util::unsigned::<usize>(10)
+            .then_ignore(just('x'))
+            .then(util::unsigned::<usize>(10));
+
+        let region = region_dim
+            .then_ignore(just(": "))
+            .then(util::spanned(
+                util::unsigned::<usize>(10)
+                    .separated_by(just(' '))
+                    .collect::<Vec<_>>(),
+            ))
+            .map(|((w, h), v)| (w, h, v));
+
+        let regions = region
+            .separated_by(text::newline())
+            .allow_trailing()
+            .collect::<Vec<_>>();
+
+        shapes
+            .then(regions)
+            .validate(|(shapes, regions), _, emitter| {
+                Self::validate(&shapes, &regions, emitter);
+                Self {
+                    shapes: shapes.into_iter().map(|(_, shape)| shape).collect(),
+                    regions: regions
+                        .into_iter()
+                        .map(|(w, h, quantities)| (w, h, quantities.value))
+                        .collect(),
+                }
+            })
+    }
+
+    // Shapes should be numbered in order from 0, and every region should list a quantity
+    // for each of them
+    fn validate<'src>(
+        shapes: &[(Spanned<usize>, Polyomino)],
+        regions: &[(usize, usize, Spanned<Vec<usize>>)],
+        emitter: &mut Emitter<Rich<'src, char>>,
+    ) {
+        for (i, (index, _)) in shapes.iter().enumerate() {
+            if index.value != i {
+                let msg = format!("expected shape {i}, but found shape {}", index.value);
+                emitter.emit(Rich::custom(index.span, msg));
+            }
+        }
+
+        for (_, _, quantities) in regions {
+            if quantities.len() != shapes.len() {
+                let msg = format!(
+                    "region lists {} quantities for {} shapes",
+                    quantities.len(),
+                    shapes.len()
+                );
+                emitter.emit(Rich::custom(quantities.span, msg));
+            }
+        }
+    }
+
+    // Every region is checked before any are packed, as packing is the slow part
+    fn packings(&self) -> Result<Vec<Option<Packing>>, Error> {
+        for (i, (_, _, quantities)) in self.regions.iter().enumerate() {
+            if quantities.len() != self.shapes.len() {
+                return Err(Error::QuantityMismatch {
+                    region: i + 1,
+                    quantities: quantities.len(),
+                    shapes: self.shapes.len(),
+                });
+            }
+        }
+
+        Ok(parallel::map(&self.regions, |_, (w, h, quantities)| {
+            polyomino::pack(*w, *h, &self.shapes, quantities)
+        }))
+    }
+
+    fn part_one(&self) -> Result<usize, Error> {
+        let packings = self.packings()?;
+        Ok(packings.iter().filter(|packing| packing.is_some()).count())
+    }
+
+    // fn part_two(&self) -> ! {
+    //     todo!();
+    // }
+}
+
+// There is no part two on the last day
+fn solve(input_file: &InputFile) -> Result<(usize,)> {
+    let input = Input::parse(input_file)?;
+    let part_one = input.part_one().context("Failed to solve part one")?;
+    Ok((part_one,))
+}
+
+util::aoc_day!(2025, 12, solve);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const EXAMPLE: &str = indoc! {"
+        0:
+        ###
+        ##.
+        ##.
+
+        1:
+        ###
+        ##.
+        .##
+
+        2:
+        .##
+        ###
+        ##.
+
+        3:
+        ##.
+        ###
+        ##.
+
+        4:
+        ###
+        #..
+        ###
+
+        5:
+        ###
+        .#.
+        ###
+
+        4x4: 0 0 0 0 2 0
+        12x5: 1 0 1 0 2 2
+        12x5: 1 0 1 0 3 2
+    "};
+
+    #[test]
+    fn example_works() {
+        let contents = EXAMPLE;
+        let example = Input::parse(&contents.into()).unwrap();
+        assert_eq!(example.part_one().unwrap(), 2);
+
+        let packing = example.packings().unwrap().remove(0).unwrap();
+        assert_eq!(packing.placements.len(), 2);
+        assert_eq!(packing.to_string().matches(['A', 'B']).count(), 14);
+        // assert_eq!(example.part_two(), todo!());
+    }
+
+    #[test]
+    fn invalid_shapes_and_regions_are_rejected() {
+        let contents = EXAMPLE
+            .replace("4:\n", "7:\n")
+            .replace("4x4: 0 0 0 0 2 0", "4x4: 0 0 2 0");
+        let errs = Input::parser().parse(&contents).into_result().unwrap_err();
+        let errs: Vec<_> = errs
+            .iter()
+            .map(|err| (err.to_string(), &contents[err.span().into_range()]))
+            .collect();
+        assert_eq!(
+            errs,
+            [
+                ("expected shape 4, but found shape 7".to_string(), "7"),
+                (
+                    "region lists 4 quantities for 6 shapes".to_string(),
+                    "0 0 2 0"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn quantity_mismatch_is_an_error() {
+        // Parsing rejects this, so this checks the parts don't rely on it
+        let mut input = Input::parse(&EXAMPLE.into()).unwrap();
+        input.regions[0].2.truncate(4);
+        assert_eq!(
+            input.part_one(),
+            Err(Error::QuantityMismatch {
+                region: 1,
+                quantities: 4,
+                shapes: 6
+            })
+        );
+    }
+
+    util::fuzz_tests! {
+        name: "day12",
+        seed: EXAMPLE,
+        dir: crate::fuzz_dir(),
+        parser: Input::parser(),
+        parts: |input| {
+            let _ = input.part_one();
+        },
+    }
+}
@@ -0,0 +1,415 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use util::rng::Rng;
+
+// Random inputs in the same format as the real ones, for testing at larger scales. The
+// meaning of `size` depends on the day. Doubling it roughly doubles the input, except on
+// the grid days 4 and 7, where it's the side length and the input grows with its square.
+pub fn generate(day: u32, size: usize, seed: u64) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let contents = match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng, size),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        9 => day09(rng, size),
+        10 => day10(rng, size),
+        11 => day11(rng, size),
+        12 => day12(rng, size),
+        _ => return None,
+    };
+    Some(contents)
+}
+
+fn lines(items: impl IntoIterator<Item = String>) -> String {
+    items.into_iter().map(|line| line + "\n").collect()
+}
+
+// `size` rotations
+pub fn day01(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        let distance = if rng.chance(0.1) {
+            rng.range(1..=5000)
+        } else {
+            rng.range(1..=99)
+        };
+        format!("{direction}{distance}")
+    }))
+}
+
+// `size` ranges of IDs with up to ten digits
+pub fn day02(rng: &mut Rng, size: usize) -> String {
+    let ranges: Vec<String> = (0..size)
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let low = rng.range(10_u64.pow(digits - 1)..=10_u64.pow(digits) - 1);
+            let width = rng.range(0..=10_u64.pow(digits.min(6)));
+            format!("{low}-{}", low + width)
+        })
+        .collect();
+    ranges.join(",") + "\n"
+}
+
+// `size` banks of 100 batteries
+pub fn day03(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        (0..100)
+            .map(|_| char::from(b'1' + rng.below(9) as u8))
+            .collect()
+    }))
+}
+
+// A square grid with sides of `size`
+pub fn day04(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    lines((0..size).map(|_| {
+        (0..size)
+            .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+            .collect()
+    }))
+}
+
+// `size` fresh ranges and `size` available IDs
+pub fn day05(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let max_id = 1_000_000_000_000;
+    let ranges = lines((0..size).map(|_| {
+        let start = rng.range(1..=max_id);
+        let end = start + rng.range(0..=max_id / 100);
+        format!("{start}-{end}")
+    }));
+    let ids = lines((0..size).map(|_| rng.range(1..=max_id).to_string()));
+    format!("{ranges}\n{ids}")
+}
+
+// `size` problems of four numbers each. Only addition and multiplication are used so every
+// problem has an answer no matter how it is read. Numbers in a problem share an alignment
+// and get longer or shorter down the rows, so reading a column never skips a blank.
+pub fn day06(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut rows = vec![String::new(); 4];
+    let mut operators = String::new();
+
+    for i in 0..size {
+        if i > 0 {
+            for row in rows.iter_mut() {
+                row.push(' ');
+            }
+            operators.push(' ');
+        }
+
+        let mut digits: Vec<u32> = (0..rows.len()).map(|_| rng.range(1..=4) as u32).collect();
+        digits.sort();
+        if rng.chance(0.5) {
+            digits.reverse();
+        }
+        let width = *digits.iter().max().unwrap() as usize;
+        let left = rng.chance(0.5);
+
+        for (row, digits) in rows.iter_mut().zip(digits) {
+            let number = rng.range(10_u64.pow(digits - 1)..=10_u64.pow(digits) - 1);
+            if left {
+                write!(row, "{number:<width$}").unwrap();
+            } else {
+                write!(row, "{number:>width$}").unwrap();
+            }
+        }
+
+        let operator = if rng.chance(0.5) { '+' } else { '*' };
+        write!(operators, "{operator:<width$}").unwrap();
+    }
+
+    rows.push(operators);
+    lines(rows)
+}
+
+// A manifold `2 * size + 1` wide and `2 * size` tall with the start at the top middle
+pub fn day07(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let width = 2 * size + 1;
+    let mut grid = vec![vec!['.'; width]; 2 * size];
+    grid[0][size] = 'S';
+
+    for row in grid.iter_mut().skip(2).step_by(2) {
+        for col in 0..width {
+            if (col == 0 || row[col - 1] != '^') && rng.chance(0.3) {
+                row[col] = '^';
+            }
+        }
+    }
+
+    lines(grid.into_iter().map(String::from_iter))
+}
+
+// Part one makes 1000 connections and multiplies the sizes of the three largest circuits
+// left, which needs at least this many boxes spread over the clusters below
+pub const DAY08_MIN_BOXES: usize = 100;
+
+// `size` junction boxes, or `DAY08_MIN_BOXES` if that's more, split between four clusters
+// at alternate corners of the space. The clusters are much smaller than the gaps between
+// them, so the 1000 shortest connections all stay inside clusters and leave at least four
+// circuits.
+pub fn day08(rng: &mut Rng, size: usize) -> String {
+    let corners = [(0, 0, 0), (1, 1, 0), (1, 0, 1), (0, 1, 1)];
+    lines((0..size.max(DAY08_MIN_BOXES)).map(|i| {
+        let (cx, cy, cz) = corners[i % corners.len()];
+        let mut coordinate = |corner: u64| corner * 90_000 + rng.below(10_000);
+        let (x, y, z) = (coordinate(cx), coordinate(cy), coordinate(cz));
+        format!("{x},{y},{z}")
+    }))
+}
+
+// A histogram-shaped loop with `size` bars standing on y = 1. Coordinates are at least 1
+// and bars next to each other have different heights, so every corner is a red tile.
+pub fn day09(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut xs: Vec<u64> = Vec::new();
+    let mut used = HashSet::new();
+    while xs.len() < size + 1 {
+        let x = rng.range(1..=100_000);
+        if used.insert(x) {
+            xs.push(x);
+        }
+    }
+    xs.sort();
+
+    let mut heights: Vec<u64> = Vec::new();
+    while heights.len() < size {
+        let h = rng.range(2..=100_000);
+        if heights.last() != Some(&h) {
+            heights.push(h);
+        }
+    }
+
+    let mut tiles = vec![(xs[0], 1)];
+    for (i, &h) in heights.iter().enumerate() {
+        tiles.push((xs[i], h));
+        tiles.push((xs[i + 1], h));
+    }
+    tiles.push((xs[size], 1));
+
+    lines(tiles.into_iter().map(|(x, y)| format!("{x},{y}")))
+}
+
+// `size` machines. The lights and joltages come from actual button presses, so every
+// machine can be solved.
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let num_lights = rng.range(3..=10) as usize;
+        let num_buttons = rng.range(num_lights as u64 / 2..=num_lights as u64 + 3) as usize;
+
+        let buttons: Vec<Vec<usize>> = (0..num_buttons)
+            .map(|_| {
+                let mut lights: Vec<usize> = (0..num_lights).filter(|_| rng.chance(0.4)).collect();
+                if lights.is_empty() {
+                    lights.push(rng.index(num_lights));
+                }
+                lights
+            })
+            .collect();
+
+        let mut lights = vec![false; num_lights];
+        let mut joltages = vec![0; num_lights];
+        for button in &buttons {
+            let toggle = rng.chance(0.5);
+            let presses = rng.range(0..=20);
+            for &i in button {
+                lights[i] ^= toggle;
+                joltages[i] += presses;
+            }
+        }
+
+        let lights: String = lights
+            .iter()
+            .map(|&on| if on { '#' } else { '.' })
+            .collect();
+        let buttons: Vec<String> = buttons
+            .iter()
+            .map(|button| {
+                let indices: Vec<String> = button.iter().map(usize::to_string).collect();
+                format!("({})", indices.join(","))
+            })
+            .collect();
+        let joltages: Vec<String> = joltages.iter().map(u64::to_string).collect();
+
+        format!(
+            "[{lights}] {} {{{}}}",
+            buttons.join(" "),
+            joltages.join(",")
+        )
+    }))
+}
+
+// A DAG with `size` devices besides `out`. Devices are listed in topological order and each
+// one feeds the next, so `you`, `svr`, `fft` and `dac` all reach `out`. Only a few devices
+// feed a second one, which keeps the number of paths within a u64.
+pub fn day11(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(4);
+    let reserved = ["you", "svr", "fft", "dac", "out"];
+
+    let mut names: Vec<String> = vec![String::new(); size];
+    let mut used: HashSet<String> = reserved.iter().map(|s| s.to_string()).collect();
+    for name in names.iter_mut() {
+        loop {
+            let candidate: String = (0..3)
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect();
+            if used.insert(candidate.clone()) {
+                *name = candidate;
+                break;
+            }
+        }
+    }
+
+    names[0] = "svr".to_string();
+    let mut special = [rng.range(1..=size as u64 - 1) as usize, 0, 0];
+    while special[1] == special[0] || special[2] == special[0] || special[1] >= special[2] {
+        special[1] = rng.range(1..=size as u64 - 1) as usize;
+        special[2] = rng.range(1..=size as u64 - 1) as usize;
+    }
+    for (i, name) in special.into_iter().zip(["you", "fft", "dac"]) {
+        names[i] = name.to_string();
+    }
+    names.push("out".to_string());
+
+    let mut branches_left = 40;
+    lines((0..size).map(|i| {
+        let mut outputs = vec![i + 1];
+        if i + 2 <= size && branches_left > 0 && rng.chance(0.2) {
+            branches_left -= 1;
+            outputs.push(rng.range(i as u64 + 2..=(i + 10).min(size) as u64) as usize);
+        }
+        let outputs: Vec<&str> = outputs.iter().map(|&j| names[j].as_str()).collect();
+        format!("{}: {}", names[i], outputs.join(" "))
+    }))
+}
+
+// Six shapes and `size` regions. Some regions fit their presents in a grid of 3x3 blocks
+// and some have more present area than space, which are both decided without searching.
+// The rest are small regions with room for the presents' area but not a block for each,
+// so the packing search has to decide them.
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let mut contents = String::new();
+    let mut areas = Vec::new();
+
+    for i in 0..6 {
+        let mut cells = [false; 9];
+        let area = rng.range(5..=7) as usize;
+        let mut order: Vec<usize> = (0..9).collect();
+        rng.shuffle(&mut order);
+        for &c in &order[..area] {
+            cells[c] = true;
+        }
+        areas.push(area);
+
+        writeln!(contents, "{i}:").unwrap();
+        for row in cells.chunks(3) {
+            let row: String = row.iter().map(|&c| if c { '#' } else { '.' }).collect();
+            writeln!(contents, "{row}").unwrap();
+        }
+        contents.push('\n');
+    }
+
+    for _ in 0..size {
+        let search = rng.chance(0.3);
+        let (width, height) = if search {
+            (rng.range(6..=10) as usize, rng.range(6..=10) as usize)
+        } else {
+            (
+                rng.range(4..=16) as usize * 3,
+                rng.range(4..=16) as usize * 3,
+            )
+        };
+        let blocks = (width / 3) * (height / 3);
+        let mut quantities = [0; 6];
+
+        if search {
+            let mut area = 0;
+            for _ in 0..=blocks {
+                let shape = rng.index(6);
+                if area + areas[shape] > width * height {
+                    break;
+                }
+                quantities[shape] += 1;
+                area += areas[shape];
+            }
+        } else if rng.chance(0.5) {
+            for _ in 0..rng.range(1..=blocks as u64) {
+                quantities[rng.index(6)] += 1;
+            }
+        } else {
+            let mut area = 0;
+            while area <= width * height {
+                let shape = rng.index(6);
+                quantities[shape] += 1;
+                area += areas[shape];
+            }
+        }
+
+        let quantities: Vec<String> = quantities.iter().map(usize::to_string).collect();
+        writeln!(contents, "{width}x{height}: {}", quantities.join(" ")).unwrap();
+    }
+
+    contents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use anyhow::{Context, Result};
+    use util::day::Day;
+    use util::{InputFile, parallel};
+
+    type Check = fn(&[u128]) -> bool;
+    type Reference = fn(&InputFile) -> Result<Vec<String>>;
+
+    // Each day's generator at a size that solves quickly in debug builds, along with what
+    // should hold for the answers and a slower solution to compare them against, if the
+    // day has one that can handle inputs this large
+    #[test]
+    fn generated_inputs_are_solved() {
+        let days: [(Day, usize, Check, Option<Reference>); 13] = [
+            (day01::DAY, 200, |a| a[1] >= a[0], None),
+            (day02::DAY, 5, |a| a[1] >= a[0], Some(day02::reference)),
+            (day03::DAY, 50, |a| a[1] >= a[0], Some(day03::reference)),
+            (day04::DAY, 30, |a| a[1] >= a[0], None),
+            (day05::DAY, 100, |a| a[0] <= 100, None),
+            (day06::DAY, 50, |_| true, None),
+            (day07::DAY, 20, |_| true, None),
+            // Raised to the fewest boxes part one can solve
+            (day08::DAY, 1, |a| a[0] > 0, None),
+            (day08::DAY, 200, |a| a[0] > 0, None),
+            (day09::DAY, 30, |a| a[1] <= a[0], None),
+            (day10::DAY, 10, |_| true, None),
+            (day11::DAY, 200, |a| a[0] >= 1 && a[1] >= 1, None),
+            (day12::DAY, 20, |a| a[0] <= 20, None),
+        ];
+
+        for (day, size, check, reference) in days {
+            for seed in 0..5 {
+                let contents = generate(day.day, size, seed).unwrap();
+                let file = InputFile::from(contents.as_str());
+                let context = || format!("day {} with seed {seed}", day.day);
+
+                let answers = (day.solve)(&file).with_context(context).unwrap();
+                let numbers: Vec<u128> = answers.iter().map(|a| a.parse().unwrap()).collect();
+                assert!(check(&numbers), "{}: {answers:?}", context());
+
+                let sequential = parallel::sequential(|| (day.solve)(&file));
+                assert_eq!(sequential.unwrap(), answers, "{}", context());
+
+                if let Some(reference) = reference {
+                    let expected = reference(&file).with_context(context).unwrap();
+                    assert_eq!(expected, answers, "{}", context());
+                }
+            }
+        }
+    }
+}
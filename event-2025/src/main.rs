@@ -1,3 +1,83 @@
-fn main() {
-    println!("Hello, world!");
+use anyhow::{Context, Result, bail};
+use event_2025::{generate, watch};
+use util::runner::{self, Job};
+use util::{day, parallel};
+
+const USAGE: &str = "Usage: event-2025 gen <day> [--size <n>] [--seed <n>]
+       event-2025 watch <day>
+       event-2025 list
+       event-2025 run [--threads <n>] [<day>...]";
+
+fn generate(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let day: u32 = args
+        .next()
+        .context(USAGE)?
+        .parse()
+        .context("Day should be a number")?;
+    let mut size = 100;
+    let mut seed = 0;
+
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .with_context(|| format!("Missing value for '{arg}'"))?;
+        match arg.as_str() {
+            "--size" => size = value.parse().context("Size should be a number")?,
+            "--seed" => seed = value.parse().context("Seed should be a number")?,
+            _ => bail!("Unknown argument '{arg}'\n{USAGE}"),
+        }
+    }
+
+    let contents = generate::generate(day, size, seed)
+        .with_context(|| format!("No generator for day {day}"))?;
+    print!("{contents}");
+
+    Ok(())
+}
+
+// Solves the given days, or every day, on their own inputs and prints a table of answers
+fn run(args: impl Iterator<Item = String>) -> Result<()> {
+    let mut args: Vec<String> = args.collect();
+    parallel::threads_arg(&mut args)?;
+    let days = args
+        .into_iter()
+        .map(|arg| arg.parse().context("Day should be a number"))
+        .collect::<Result<Vec<u32>>>()?;
+    let registered = day::days();
+    if let Some(missing) = days
+        .iter()
+        .find(|&&d| !registered.iter().any(|r| r.day == d))
+    {
+        bail!("No solution for day {missing}");
+    }
+
+    let jobs: Vec<Job> = registered
+        .into_iter()
+        .filter(|r| days.is_empty() || days.contains(&r.day))
+        .map(|r| r.job())
+        .collect();
+    runner::report(&jobs)
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("gen") => generate(args),
+        Some("watch") => {
+            let day = args
+                .next()
+                .context(USAGE)?
+                .parse()
+                .context("Day should be a number")?;
+            watch::watch(day)
+        }
+        Some("list") => {
+            for day in day::days() {
+                println!("{}/{:02}", day.year, day.day);
+            }
+            Ok(())
+        }
+        Some("run") => run(args),
+        _ => bail!(USAGE),
+    }
 }
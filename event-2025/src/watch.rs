@@ -0,0 +1,135 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+// Re-runs a day whenever its solution or input changes: builds it, runs the example tests,
+// then solves the real input and shows how the answers changed since the last run
+pub fn watch(day: u32) -> Result<()> {
+    let name = format!("day{day:02}");
+    let source = format!("event-2025/src/{name}.rs");
+    let input = format!("event-2025/input/{name}.txt");
+    if !Path::new(&source).is_file() {
+        bail!("No solution at '{source}'");
+    }
+
+    // Editors often save by replacing the file, which would end a watch on the file itself,
+    // so watch the directories instead
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start watching")?;
+    for path in [&source, &input] {
+        let dir = Path::new(path).parent().unwrap();
+        if dir.is_dir() {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .with_context(|| format!("Failed to watch '{}'", dir.display()))?;
+        }
+    }
+
+    let watched = |event: &Event| {
+        !matches!(event.kind, EventKind::Access(_))
+            && event.paths.iter().any(|path| {
+                let path = path.to_string_lossy();
+                path.ends_with(&source) || path.ends_with(&input)
+            })
+    };
+
+    let mut answers = Vec::new();
+    loop {
+        // Clear the terminal so only the latest run is on screen
+        print!("\x1b[2J\x1b[H");
+        println!("Watching {source} and {input}\n");
+        if let Some(latest) = run(&name)? {
+            print!("{}", diff(&answers, &latest));
+            answers = latest;
+        }
+
+        loop {
+            let event = rx.recv().context("Stopped watching")?;
+            if event.is_ok_and(|event| watched(&event)) {
+                break;
+            }
+        }
+        // Saving often fires a few events in a row, so wait for them to settle
+        while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+    }
+}
+
+// Builds, tests and runs the day, returning its answers if every step passed
+fn run(name: &str) -> Result<Option<Vec<String>>> {
+    let cargo = |args: &[&str]| {
+        let mut command = Command::new("cargo");
+        command.args(args).args(["-q", "-p", "event-2025"]);
+        command
+    };
+
+    let built = cargo(&["build", "--bin", name])
+        .status()
+        .context("Failed to run cargo")?;
+    if !built.success() {
+        return Ok(None);
+    }
+
+    let tested = cargo(&["test", "--lib"])
+        .arg(format!("{name}::tests::example"))
+        .stdout(Stdio::null())
+        .status()
+        .context("Failed to run cargo")?;
+    if !tested.success() {
+        println!("Example tests failed");
+        return Ok(None);
+    }
+    println!("Example tests passed\n");
+
+    let output = cargo(&["run", "--bin", name])
+        .stderr(Stdio::inherit())
+        .output()
+        .context("Failed to run cargo")?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(Some(stdout.lines().map(String::from).collect()))
+}
+
+// One line per answer, marking the ones that changed since the previous run
+fn diff(previous: &[String], latest: &[String]) -> String {
+    let mut result = String::new();
+    for (i, answer) in latest.iter().enumerate() {
+        match previous.get(i) {
+            Some(old) if old != answer => writeln!(result, "~ {answer} (was {old})"),
+            None if !previous.is_empty() => writeln!(result, "+ {answer}"),
+            _ => writeln!(result, "  {answer}"),
+        }
+        .unwrap();
+    }
+    for old in previous.iter().skip(latest.len()) {
+        writeln!(result, "- {old}").unwrap();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(answers: &[&str]) -> Vec<String> {
+        answers.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn diff_marks_changed_answers() {
+        assert_eq!(diff(&[], &lines(&["50", "24"])), "  50\n  24\n");
+        assert_eq!(
+            diff(&lines(&["50", "24"]), &lines(&["50", "25"])),
+            "  50\n~ 25 (was 24)\n"
+        );
+        assert_eq!(diff(&lines(&["50"]), &lines(&["50", "24"])), "  50\n+ 24\n");
+        assert_eq!(diff(&lines(&["50", "24"]), &lines(&["50"])), "  50\n- 24\n");
+    }
+}
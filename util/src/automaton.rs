@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighborhood {
+    // The eight surrounding cells
+    Moore,
+    // The four orthogonally adjacent cells
+    VonNeumann,
+}
+
+impl Neighborhood {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Neighborhood::Moore => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+            Neighborhood::VonNeumann => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<C> {
+    pub row: usize,
+    pub col: usize,
+    pub from: C,
+    pub to: C,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    // The grid stopped changing after this many generations
+    FixedPoint { generation: usize },
+    // The grid at the current generation repeats the one at `start`
+    Cycle { start: usize, period: usize },
+    // Ran out of generations first
+    Limit,
+}
+
+// A grid where every cell updates at the same time from its own value and its in-bounds
+// neighbors. Only cells next to a change are re-evaluated, so a generation costs time
+// proportional to what changed rather than to the size of the grid.
+pub struct Automaton<C, F> {
+    grid: Vec<Vec<C>>,
+    neighborhood: Neighborhood,
+    rule: F,
+    pending: Vec<(usize, usize)>,
+    queued: Vec<Vec<bool>>,
+    history: Vec<Vec<Change<C>>>,
+    // The generations seen so far by the hash of their grid. Hashes can collide, so a
+    // match is only a cycle once the grids are compared.
+    hash: u64,
+    seen: HashMap<u64, Vec<usize>>,
+}
+
+impl<C, F> Automaton<C, F>
+where
+    C: Clone + Eq + Hash,
+    F: Fn(&C, &[&C]) -> C,
+{
+    pub fn new(grid: Vec<Vec<C>>, neighborhood: Neighborhood, rule: F) -> Self {
+        let pending = (0..grid.len())
+            .flat_map(|r| (0..grid[r].len()).map(move |c| (r, c)))
+            .collect();
+        let queued = grid.iter().map(|row| vec![true; row.len()]).collect();
+
+        let hash = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, cell)| (r, c, cell)))
+            .fold(0, |acc: u64, (r, c, cell)| {
+                acc.wrapping_add(Self::cell_hash(r, c, cell))
+            });
+
+        Self {
+            grid,
+            neighborhood,
+            rule,
+            pending,
+            queued,
+            history: Vec::new(),
+            hash,
+            seen: HashMap::from([(hash, vec![0])]),
+        }
+    }
+
+    // The grid hash is a sum over cells, so a change only has to update its own term
+    fn cell_hash(row: usize, col: usize, cell: &C) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (row, col, cell).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn neighbors(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighborhood
+            .offsets()
+            .iter()
+            .filter_map(move |&(dr, dc)| {
+                let r = row.checked_add_signed(dr)?;
+                let c = col.checked_add_signed(dc)?;
+                (c < self.grid.get(r)?.len()).then_some((r, c))
+            })
+    }
+
+    pub fn grid(&self) -> &[Vec<C>] {
+        &self.grid
+    }
+
+    pub fn generation(&self) -> usize {
+        self.history.len()
+    }
+
+    // The cells that changed to produce each generation
+    pub fn history(&self) -> &[Vec<Change<C>>] {
+        &self.history
+    }
+
+    pub fn step(&mut self) -> &[Change<C>] {
+        let mut changes = Vec::new();
+
+        for &(row, col) in &self.pending {
+            self.queued[row][col] = false;
+            let neighbors: Vec<&C> = self
+                .neighbors(row, col)
+                .map(|(r, c)| &self.grid[r][c])
+                .collect();
+            let cell = &self.grid[row][col];
+            let next = (self.rule)(cell, &neighbors);
+
+            if next != *cell {
+                changes.push(Change {
+                    row,
+                    col,
+                    from: cell.clone(),
+                    to: next,
+                });
+            }
+        }
+
+        let mut pending = Vec::new();
+        for change in &changes {
+            self.hash = self
+                .hash
+                .wrapping_sub(Self::cell_hash(change.row, change.col, &change.from))
+                .wrapping_add(Self::cell_hash(change.row, change.col, &change.to));
+            self.grid[change.row][change.col] = change.to.clone();
+
+            let cells = std::iter::once((change.row, change.col))
+                .chain(self.neighbors(change.row, change.col))
+                .collect::<Vec<_>>();
+            for (r, c) in cells {
+                if !self.queued[r][c] {
+                    self.queued[r][c] = true;
+                    pending.push((r, c));
+                }
+            }
+        }
+
+        self.pending = pending;
+        self.history.push(changes);
+        self.history.last().unwrap()
+    }
+
+    pub fn run(&mut self, max_generations: usize) -> Outcome {
+        for _ in 0..max_generations {
+            if self.step().is_empty() {
+                // The last step changed nothing, so it doesn't count as a generation
+                self.history.pop();
+                return Outcome::FixedPoint {
+                    generation: self.generation(),
+                };
+            }
+
+            let generation = self.generation();
+            let cycle = self.seen.get(&self.hash).and_then(|earlier| {
+                earlier
+                    .iter()
+                    .copied()
+                    .find(|&start| self.snapshot(start) == self.grid)
+            });
+            if let Some(start) = cycle {
+                return Outcome::Cycle {
+                    start,
+                    period: generation - start,
+                };
+            }
+            self.seen.entry(self.hash).or_default().push(generation);
+        }
+
+        Outcome::Limit
+    }
+
+    // Rebuilds an earlier generation by undoing the changes made since
+    pub fn snapshot(&self, generation: usize) -> Vec<Vec<C>> {
+        let mut grid = self.grid.clone();
+        for change in self.history[generation..].iter().rev().flatten() {
+            grid[change.row][change.col] = change.from.clone();
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cell: &bool, neighbors: &[&bool]) -> bool {
+        let alive = neighbors.iter().filter(|&&&n| n).count();
+        alive == 3 || (*cell && alive == 2)
+    }
+
+    fn grid(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn blinker_cycles() {
+        let start = grid(&[".....", "..#..", "..#..", "..#..", "....."]);
+        let mut automaton = Automaton::new(start.clone(), Neighborhood::Moore, life);
+
+        assert_eq!(
+            automaton.run(10),
+            Outcome::Cycle {
+                start: 0,
+                period: 2
+            }
+        );
+        assert_eq!(automaton.generation(), 2);
+        assert_eq!(automaton.snapshot(0), start);
+        assert_eq!(
+            automaton.snapshot(1),
+            grid(&[".....", ".....", ".###.", ".....", "....."])
+        );
+    }
+
+    #[test]
+    fn hash_collisions_are_not_cycles() {
+        let count = |cell: &u8, _: &[&u8]| (*cell + 1).min(3);
+        let mut automaton = Automaton::new(vec![vec![0]], Neighborhood::Moore, count);
+
+        // Pretend the grid at generation 2 hashes the same as the starting grid
+        let hash = Automaton::<u8, fn(&u8, &[&u8]) -> u8>::cell_hash(0, 0, &2);
+        automaton.seen.insert(hash, vec![0]);
+
+        assert_eq!(automaton.run(10), Outcome::FixedPoint { generation: 3 });
+    }
+
+    #[test]
+    fn block_is_fixed_point() {
+        let start = grid(&["....", ".##.", ".#..", "...."]);
+        let mut automaton = Automaton::new(start, Neighborhood::Moore, life);
+
+        assert_eq!(automaton.run(10), Outcome::FixedPoint { generation: 1 });
+        assert_eq!(automaton.grid(), grid(&["....", ".##.", ".##.", "...."]));
+        assert_eq!(automaton.history()[0].len(), 1);
+    }
+}
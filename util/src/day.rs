@@ -0,0 +1,158 @@
+use std::fmt::Display;
+
+use anyhow::{Result, bail};
+
+use crate::runner::{self, Job, Solve};
+use crate::{InputFile, parallel};
+
+// Handles any arguments other than `--threads` and `--batch`
+pub type Cli = fn(&InputFile, &[String]) -> Result<()>;
+
+// A solution registered with `aoc_day!`. Every day linked into a binary can be found
+// with `days`, so runners don't need a list of them.
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub solve: Solve,
+    pub cli: Option<Cli>,
+    // The input, when it was compiled in with the `embed-inputs` feature
+    pub embedded: Option<&'static str>,
+}
+
+inventory::collect!(Day);
+
+impl Day {
+    pub fn input_path(&self) -> String {
+        format!("event-{}/input/day{:02}.txt", self.year, self.day)
+    }
+
+    pub fn job(&self) -> Job {
+        Job {
+            path: self.input_path().into(),
+            embedded: self.embedded,
+            solve: self.solve,
+        }
+    }
+}
+
+// Every registered day, in order
+pub fn days() -> Vec<&'static Day> {
+    let mut days: Vec<&Day> = inventory::iter::<Day>.into_iter().collect();
+    days.sort_by_key(|day| (day.year, day.day));
+    days
+}
+
+// The answers from a day's `solve`, which has one answer on days with only one part
+pub trait Answers {
+    fn answers(self) -> Vec<String>;
+}
+
+impl<A: Display> Answers for (A,) {
+    fn answers(self) -> Vec<String> {
+        vec![self.0.to_string()]
+    }
+}
+
+impl<A: Display, B: Display> Answers for (A, B) {
+    fn answers(self) -> Vec<String> {
+        vec![self.0.to_string(), self.1.to_string()]
+    }
+}
+
+// The entry point `aoc_main!` gives each day's binary
+pub fn main(day: &Day) -> Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    parallel::threads_arg(&mut args)?;
+    if let Some(inputs) = runner::batch_arg(&args) {
+        return runner::batch(inputs, day.solve);
+    }
+
+    let input_file = match day.embedded {
+        Some(contents) => InputFile::embedded(&day.input_path(), contents),
+        None => InputFile::read(day.input_path())?,
+    };
+    if let Some(arg) = args.first() {
+        let Some(cli) = day.cli else {
+            bail!("Unknown argument '{arg}'");
+        };
+        return cli(&input_file, &args);
+    }
+
+    for answer in (day.solve)(&input_file)? {
+        println!("{answer}");
+    }
+    Ok(())
+}
+
+// Declares a day's module as the solution to `$day`, as the `DAY` constant, and registers
+// it with `days`. `$cli` handles any extra arguments. With the `embed-inputs` feature, the
+// input comes from `input-$day.rs` in the crate's `OUT_DIR`, which its build script writes.
+#[macro_export]
+macro_rules! aoc_day {
+    ($year:literal, $day:literal, $solve:path) => {
+        $crate::aoc_day!(@register $year, $day, $solve, None);
+    };
+    ($year:literal, $day:literal, $solve:path, $cli:path) => {
+        $crate::aoc_day!(@register $year, $day, $solve, Some($cli));
+    };
+    (@register $year:literal, $day:literal, $solve:path, $cli:expr) => {
+        #[cfg(feature = "embed-inputs")]
+        const EMBEDDED_INPUT: Option<&str> =
+            Some(include!(concat!(env!("OUT_DIR"), "/input-", $day, ".rs")));
+        #[cfg(not(feature = "embed-inputs"))]
+        const EMBEDDED_INPUT: Option<&str> = None;
+
+        pub const DAY: $crate::day::Day = $crate::day::Day {
+            year: $year,
+            day: $day,
+            solve: |file| Ok($crate::day::Answers::answers($solve(file)?)),
+            cli: $cli,
+            embedded: EMBEDDED_INPUT,
+        };
+
+        $crate::inventory::submit! { DAY }
+    };
+}
+
+// Gives a day's binary a `main` that solves its input with the `DAY` declared in `$module`
+#[macro_export]
+macro_rules! aoc_main {
+    ($($module:ident)::+) => {
+        fn main() -> $crate::anyhow::Result<()> {
+            $crate::day::main(&$($module)::+::DAY)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(file: &InputFile) -> Result<(usize, &'static str)> {
+        Ok((file.contents.len(), "two"))
+    }
+
+    // What `aoc_day!` declares, which can't be used here as it checks the features of the
+    // crate it's used in
+    const DAY: Day = Day {
+        year: 1999,
+        day: 7,
+        solve: |file| Ok(Answers::answers(solve(file)?)),
+        cli: None,
+        embedded: None,
+    };
+
+    inventory::submit! { DAY }
+
+    #[test]
+    fn days_are_registered() {
+        let day = days()
+            .into_iter()
+            .find(|day| day.year == 1999 && day.day == 7)
+            .unwrap();
+        assert_eq!(day.input_path(), "event-1999/input/day07.txt");
+        assert_eq!((day.solve)(&"abc".into()).unwrap(), ["3", "two"]);
+        assert!(day.cli.is_none());
+        assert!(day.embedded.is_none());
+    }
+}
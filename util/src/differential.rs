@@ -0,0 +1,238 @@
+use std::fmt::Debug;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::parallel;
+use crate::rng::Rng;
+
+// Checks an optimized solution against a simpler reference on random inputs. Both take the
+// input text and return `None` when they reject it, which mostly happens to the broken
+// inputs tried while shrinking.
+pub struct Differential<T> {
+    pub name: &'static str,
+    pub generate: fn(&mut Rng) -> String,
+    pub reference: fn(&str) -> Option<T>,
+    pub optimized: fn(&str) -> Option<T>,
+}
+
+impl<T: PartialEq + Debug> Differential<T> {
+    // Replays the saved regressions for this check, then tries `cases` generated inputs.
+    // The first disagreement is shrunk, saved to `regressions` and reported by panicking.
+    pub fn run(&self, cases: u64, regressions: &Path) {
+        for (path, input) in load(regressions, self.name) {
+            if let Some((reference, optimized)) = self.disagreement(&input) {
+                panic!(
+                    "{} disagrees on regression '{}'\n{input}\nreference: {reference:?}\noptimized: {optimized:?}",
+                    self.name,
+                    path.display()
+                );
+            }
+        }
+
+        for seed in 0..cases {
+            let input = (self.generate)(&mut Rng::new(seed));
+            if self.disagreement(&input).is_none() {
+                continue;
+            }
+
+            let input = self.shrink(&input);
+            let (reference, optimized) = self.disagreement(&input).unwrap();
+            let path = save(regressions, self.name, &input);
+            panic!(
+                "{} disagrees on seed {seed}, shrunk and saved to '{}'\n{input}\nreference: {reference:?}\noptimized: {optimized:?}",
+                self.name,
+                path.display()
+            );
+        }
+    }
+
+    // The optimized solution also has to give the same answer when its `parallel::map`s
+    // run in turn, which is reported as the reference
+    fn disagreement(&self, input: &str) -> Option<(T, T)> {
+        let reference = (self.reference)(input)?;
+        let optimized = (self.optimized)(input)?;
+        if reference != optimized {
+            return Some((reference, optimized));
+        }
+        let sequential = parallel::sequential(|| (self.optimized)(input))?;
+        (sequential != optimized).then_some((sequential, optimized))
+    }
+
+    // Greedily takes the first smaller input that still disagrees until none of them do
+    pub fn shrink(&self, input: &str) -> String {
+        let mut input = input.to_string();
+        while let Some(smaller) = candidates(&input)
+            .into_iter()
+            .find(|candidate| self.disagreement(candidate).is_some())
+        {
+            input = smaller;
+        }
+        input
+    }
+}
+
+// The saved inputs in `dir` for the check called `name`, in a fixed order
+pub(crate) fn load(dir: &Path, name: &str) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let prefix = format!("{name}-");
+    let mut saved: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        })
+        .filter_map(|path| fs::read_to_string(&path).ok().map(|input| (path, input)))
+        .collect();
+    saved.sort();
+    saved
+}
+
+// Names the file after a hash of the input that stays the same between builds, so the
+// same input is only ever saved once
+pub(crate) fn save(dir: &Path, name: &str, input: &str) -> PathBuf {
+    let path = dir.join(format!("{name}-{:016x}.txt", fnv1a(input)));
+    fs::create_dir_all(dir)
+        .and_then(|()| fs::write(&path, input))
+        .unwrap_or_else(|err| panic!("Failed to save '{}': {err}", path.display()));
+    path
+}
+
+fn fnv1a(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+// Smaller versions of `input`, roughly from the biggest cut to the smallest: dropping runs
+// of lines, dropping one comma or space separated item from a line, making a number
+// smaller, then dropping a single character
+pub(crate) fn candidates(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let join =
+        |lines: &[&str]| -> String { lines.iter().map(|line| format!("{line}\n")).collect() };
+    let mut candidates = Vec::new();
+
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        for start in (0..lines.len()).step_by(chunk) {
+            let end = (start + chunk).min(lines.len());
+            candidates.push(join(&[&lines[..start], &lines[end..]].concat()));
+        }
+        chunk /= 2;
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        for separator in [',', ' '] {
+            let items: Vec<&str> = line.split(separator).collect();
+            if items.len() < 2 {
+                continue;
+            }
+            for j in 0..items.len() {
+                let mut items = items.clone();
+                items.remove(j);
+                let mut lines = lines.clone();
+                let line = items.join(&separator.to_string());
+                lines[i] = &line;
+                candidates.push(join(&lines));
+            }
+        }
+    }
+
+    let mut start = None;
+    for (i, c) in input.char_indices().chain([(input.len(), '\n')]) {
+        match (start, c.is_ascii_digit()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                if let Ok(value) = input[s..i].parse::<u128>() {
+                    let mut smaller = vec![0, 1, value / 2, value / 10];
+                    smaller.sort();
+                    smaller.dedup();
+                    for v in smaller.into_iter().filter(|&v| v < value) {
+                        candidates.push(format!("{}{v}{}", &input[..s], &input[i..]));
+                    }
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    for (i, c) in input.char_indices() {
+        candidates.push(format!("{}{}", &input[..i], &input[i + c.len_utf8()..]));
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum(input: &str) -> Option<u64> {
+        input.lines().map(|line| line.parse::<u64>().ok()).sum()
+    }
+
+    // Gets every number above 50 wrong
+    fn broken_sum(input: &str) -> Option<u64> {
+        input
+            .lines()
+            .map(|line| line.parse::<u64>().ok().map(|x| x.min(50)))
+            .sum()
+    }
+
+    fn numbers(rng: &mut Rng) -> String {
+        (0..10)
+            .map(|_| format!("{}\n", rng.range(0..=60)))
+            .collect()
+    }
+
+    #[test]
+    fn shrink_finds_minimal_input() {
+        let check = Differential {
+            name: "sum",
+            generate: numbers,
+            reference: sum,
+            optimized: broken_sum,
+        };
+        assert_eq!(check.shrink("3\n12\n70\n5\n"), "70");
+        assert_eq!(check.shrink("3\n4\n"), "3\n4\n");
+    }
+
+    #[test]
+    fn disagreements_are_saved_and_replayed() {
+        let dir = std::env::temp_dir().join(format!("differential-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let agreeing = Differential {
+            name: "sum",
+            generate: numbers,
+            reference: sum,
+            optimized: sum,
+        };
+        agreeing.run(100, &dir);
+        assert!(!dir.exists());
+
+        let broken = Differential {
+            optimized: broken_sum,
+            ..agreeing
+        };
+        assert!(std::panic::catch_unwind(|| broken.run(100, &dir)).is_err());
+        let saved: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(saved.len(), 1);
+        let saved = fs::read_to_string(&saved[0]).unwrap();
+        assert_eq!(saved.lines().count(), 1);
+        assert!(saved.trim().parse::<u64>().unwrap() > 50);
+
+        // The regression fails even when no new cases are generated
+        assert!(std::panic::catch_unwind(|| broken.run(0, &dir)).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
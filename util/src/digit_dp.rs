@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, Sub};
+
+// Reads a number's digits from most to least significant and decides whether to accept
+// it. Numbers are written without leading zeros, except for zero itself.
+pub trait DigitAutomaton {
+    type State: Clone + Eq + Hash;
+
+    // The state before the first digit of a number with `len` digits
+    fn start(&self, len: usize) -> Self::State;
+
+    // Returning `None` rejects every number with this prefix
+    fn step(&self, state: &Self::State, position: usize, digit: u32) -> Option<Self::State>;
+
+    fn accepts(&self, state: &Self::State) -> bool;
+}
+
+// Tallies panic rather than wrap when they overflow, in release builds too
+const OVERFLOW: &str = "tally overflows a u128";
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub count: u128,
+    pub sum: u128,
+}
+
+impl Add for Tally {
+    type Output = Tally;
+
+    fn add(self, rhs: Tally) -> Tally {
+        Tally {
+            count: self.count.checked_add(rhs.count).expect(OVERFLOW),
+            sum: self.sum.checked_add(rhs.sum).expect(OVERFLOW),
+        }
+    }
+}
+
+impl Sub for Tally {
+    type Output = Tally;
+
+    fn sub(self, rhs: Tally) -> Tally {
+        Tally {
+            count: self.count.checked_sub(rhs.count).expect(OVERFLOW),
+            sum: self.sum.checked_sub(rhs.sum).expect(OVERFLOW),
+        }
+    }
+}
+
+// Counts and sums the numbers in `lo..=hi` that `automaton` accepts when written in `radix`.
+// Panics if the sum overflows a u128, in release builds too.
+pub fn tally<A: DigitAutomaton>(automaton: &A, radix: u32, lo: u128, hi: u128) -> Tally {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix should be between 2 and 36"
+    );
+
+    if lo > hi {
+        return Tally::default();
+    }
+
+    let below_lo = match lo.checked_sub(1) {
+        Some(n) => tally_up_to(automaton, radix, n),
+        None => Tally::default(),
+    };
+    tally_up_to(automaton, radix, hi) - below_lo
+}
+
+fn digits(mut n: u128, radix: u32) -> Vec<u32> {
+    let mut digits = Vec::new();
+    loop {
+        digits.push((n % u128::from(radix)) as u32);
+        n /= u128::from(radix);
+        if n == 0 {
+            break;
+        }
+    }
+    digits.reverse();
+    digits
+}
+
+fn tally_up_to<A: DigitAutomaton>(automaton: &A, radix: u32, bound: u128) -> Tally {
+    let bound = digits(bound, radix);
+
+    // Shorter numbers are all below the bound, so only the longest length is constrained
+    (1..=bound.len())
+        .map(|len| {
+            let mut counter = Counter {
+                automaton,
+                radix,
+                len,
+                memo: HashMap::new(),
+            };
+            let limit = (len == bound.len()).then_some(&bound[..]);
+            counter.tally(automaton.start(len), limit)
+        })
+        .fold(Tally::default(), Add::add)
+}
+
+struct Counter<'a, A: DigitAutomaton> {
+    automaton: &'a A,
+    radix: u32,
+    len: usize,
+    // Completions of a prefix that is already below the bound, summed over the remaining
+    // digits only so the entry doesn't depend on the prefix
+    memo: HashMap<(usize, A::State), Tally>,
+}
+
+impl<A: DigitAutomaton> Counter<'_, A> {
+    fn lowest_digit(&self, position: usize) -> u32 {
+        u32::from(position == 0 && self.len > 1)
+    }
+
+    // Walks down the digits of the bound, branching off into unconstrained suffixes
+    // wherever a smaller digit is chosen. Without a bound every digit is allowed.
+    fn tally(&mut self, start: A::State, bound: Option<&[u32]>) -> Tally {
+        let Some(bound) = bound else {
+            return self.free(0, start);
+        };
+
+        let mut total = Tally::default();
+        let mut state = start;
+        let mut prefix = 0_u128;
+
+        for (position, &limit) in bound.iter().enumerate() {
+            for digit in self.lowest_digit(position)..limit {
+                if let Some(next) = self.automaton.step(&state, position, digit) {
+                    let suffix = self.free(position + 1, next);
+                    let head = prefix * u128::from(self.radix) + u128::from(digit);
+                    total = total + self.extend(head, position, suffix);
+                }
+            }
+
+            let Some(next) = self.automaton.step(&state, position, limit) else {
+                return total;
+            };
+            state = next;
+            prefix = prefix * u128::from(self.radix) + u128::from(limit);
+        }
+
+        if self.automaton.accepts(&state) {
+            total = total
+                + Tally {
+                    count: 1,
+                    sum: prefix,
+                };
+        }
+        total
+    }
+
+    // Puts the digits in `head`, ending at `position`, in front of every counted suffix
+    fn extend(&self, head: u128, position: usize, suffix: Tally) -> Tally {
+        let place = u128::from(self.radix).pow((self.len - position - 1) as u32);
+        Tally {
+            count: suffix.count,
+            sum: head
+                .checked_mul(place)
+                .and_then(|n| n.checked_mul(suffix.count))
+                .and_then(|n| n.checked_add(suffix.sum))
+                .expect(OVERFLOW),
+        }
+    }
+
+    fn free(&mut self, position: usize, state: A::State) -> Tally {
+        if position == self.len {
+            return Tally {
+                count: u128::from(self.automaton.accepts(&state)),
+                sum: 0,
+            };
+        }
+
+        let key = (position, state);
+        if let Some(&tally) = self.memo.get(&key) {
+            return tally;
+        }
+
+        let mut total = Tally::default();
+        for digit in self.lowest_digit(position)..self.radix {
+            if let Some(next) = self.automaton.step(&key.1, position, digit) {
+                let suffix = self.free(position + 1, next);
+                total = total + self.extend(digit.into(), position, suffix);
+            }
+        }
+
+        self.memo.insert(key, total);
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Accepts numbers whose digit sum is a multiple of `modulus`
+    struct DigitSum {
+        modulus: u32,
+    }
+
+    impl DigitAutomaton for DigitSum {
+        type State = u32;
+
+        fn start(&self, _len: usize) -> u32 {
+            0
+        }
+
+        fn step(&self, state: &u32, _position: usize, digit: u32) -> Option<u32> {
+            Some((state + digit) % self.modulus)
+        }
+
+        fn accepts(&self, state: &u32) -> bool {
+            *state == 0
+        }
+    }
+
+    // Accepts numbers whose digits never decrease
+    struct NonDecreasing;
+
+    impl DigitAutomaton for NonDecreasing {
+        type State = u32;
+
+        fn start(&self, _len: usize) -> u32 {
+            0
+        }
+
+        fn step(&self, state: &u32, _position: usize, digit: u32) -> Option<u32> {
+            (digit >= *state).then_some(digit)
+        }
+
+        fn accepts(&self, _state: &u32) -> bool {
+            true
+        }
+    }
+
+    fn brute_force(radix: u32, lo: u128, hi: u128, accepts: impl Fn(&[u32]) -> bool) -> Tally {
+        (lo..=hi)
+            .filter(|&n| accepts(&digits(n, radix)))
+            .fold(Tally::default(), |acc, n| acc + Tally { count: 1, sum: n })
+    }
+
+    #[test]
+    fn tally_matches_brute_force() {
+        for radix in [2, 10] {
+            for (lo, hi) in [(0, 0), (0, 1000), (37, 999), (500, 4321), (7, 3)] {
+                let digit_sum = DigitSum { modulus: 3 };
+                assert_eq!(
+                    tally(&digit_sum, radix, lo, hi),
+                    brute_force(radix, lo, hi, |d| d.iter().sum::<u32>() % 3 == 0),
+                    "digit sum in radix {radix} over {lo}..={hi}"
+                );
+
+                assert_eq!(
+                    tally(&NonDecreasing, radix, lo, hi),
+                    brute_force(radix, lo, hi, |d| d.is_sorted()),
+                    "non-decreasing in radix {radix} over {lo}..={hi}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn large_range_works() {
+        // Non-decreasing digit strings of length n are multisets, so there are
+        // C(n + 9, 9) of them including the zero-padded ones
+        let count = tally(&NonDecreasing, 10, 0, 10_u128.pow(18) - 1).count;
+        assert_eq!(count, 4686825);
+    }
+
+    #[test]
+    #[should_panic(expected = "tally overflows a u128")]
+    fn overflowing_sums_panic() {
+        tally(&DigitSum { modulus: 1 }, 10, 0, u128::MAX);
+    }
+}
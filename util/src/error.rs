@@ -0,0 +1,75 @@
+// Declares an error enum along with its `Display` and `std::error::Error` impls. Each
+// variant is followed by its message, which can use the variant's fields by name. Tuple
+// variants name their fields too, for the message's sake.
+//
+//     util::error_enum! {
+//         #[derive(Debug)]
+//         enum Error {
+//             TooFew { have: usize } => "only {have} left",
+//             Unknown(name: String) => "'{name}' is not defined",
+//             Overflow => "total overflows",
+//         }
+//     }
+#[macro_export]
+macro_rules! error_enum {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $variant:ident
+                $({ $($field:ident: $field_ty:ty),* $(,)? })?
+                $(( $($arg:ident: $arg_ty:ty),* $(,)? ))?
+                => $message:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis enum $name {
+            $(
+                $variant
+                $({ $($field: $field_ty),* })?
+                $(( $($arg_ty),* ))?
+            ),*
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    $(
+                        Self::$variant $({ $($field),* })? $(( $($arg),* ))? => {
+                            // Not every message uses every field
+                            $($(let _ = $field;)*)?
+                            $($(let _ = $arg;)*)?
+                            write!(f, $message)
+                        }
+                    )*
+                }
+            }
+        }
+
+        impl ::std::error::Error for $name {}
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    error_enum! {
+        #[derive(Debug, PartialEq, Eq)]
+        enum Error {
+            TooFew { have: usize, want: usize } => "only {have} left",
+            Unknown(name: String) => "'{name}' is not defined",
+            Overflow => "total overflows",
+        }
+    }
+
+    #[test]
+    fn messages_use_fields() {
+        let too_few = Error::TooFew { have: 2, want: 3 };
+        assert_eq!(too_few.to_string(), "only 2 left");
+        assert_eq!(Error::Unknown("x".into()).to_string(), "'x' is not defined");
+        assert_eq!(Error::Overflow.to_string(), "total overflows");
+
+        let error: Box<dyn std::error::Error> = Box::new(Error::Overflow);
+        assert_eq!(error.to_string(), "total overflows");
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt;
+use std::ops::ControlFlow;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetExhausted;
+
+impl fmt::Display for BudgetExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exact cover search ran out of its node budget")
+    }
+}
+
+impl std::error::Error for BudgetExhausted {}
+
+// An exact cover problem: pick rows so every primary column is covered exactly once and
+// every secondary column at most once. Solutions are lists of row indices.
+#[derive(Debug, Clone)]
+pub struct ExactCover {
+    num_primary: usize,
+    num_columns: usize,
+    rows: Vec<Vec<usize>>,
+    node_budget: Option<u64>,
+}
+
+impl ExactCover {
+    pub fn new(num_primary: usize, num_secondary: usize) -> Self {
+        Self {
+            num_primary,
+            num_columns: num_primary + num_secondary,
+            rows: Vec::new(),
+            node_budget: None,
+        }
+    }
+
+    // Columns `0..num_primary` are primary and the rest are secondary
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        assert!(
+            columns.iter().all(|&c| c < self.num_columns),
+            "row should only use existing columns"
+        );
+        self.rows.push(columns.to_vec());
+        self.rows.len() - 1
+    }
+
+    // Limits how many search nodes are visited before giving up
+    pub fn set_node_budget(&mut self, budget: Option<u64>) {
+        self.node_budget = budget;
+    }
+
+    pub fn for_each_solution(
+        &self,
+        mut visit: impl FnMut(&[usize]) -> ControlFlow<()>,
+    ) -> Result<(), BudgetExhausted> {
+        let mut links = Links::new(self);
+        let mut partial = Vec::new();
+        links.search(&mut partial, &mut visit).map(|_| ())
+    }
+
+    pub fn first_solution(&self) -> Result<Option<Vec<usize>>, BudgetExhausted> {
+        let mut first = None;
+        self.for_each_solution(|rows| {
+            first = Some(rows.to_vec());
+            ControlFlow::Break(())
+        })?;
+        Ok(first)
+    }
+
+    pub fn solutions(&self) -> Result<Vec<Vec<usize>>, BudgetExhausted> {
+        let mut solutions = Vec::new();
+        self.for_each_solution(|rows| {
+            solutions.push(rows.to_vec());
+            ControlFlow::Continue(())
+        })?;
+        Ok(solutions)
+    }
+
+    pub fn count_solutions(&self) -> Result<u64, BudgetExhausted> {
+        let mut count = 0;
+        self.for_each_solution(|_| {
+            count += 1;
+            ControlFlow::Continue(())
+        })?;
+        Ok(count)
+    }
+}
+
+// Uses the dancing links layout described in https://arxiv.org/abs/cs/0011047. Node 0 is
+// the root, nodes `1..=num_columns` are column headers and the rest are row entries.
+// Secondary headers are left out of the root's list so they are never chosen to branch on.
+struct Links {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    row: Vec<usize>,
+    size: Vec<usize>,
+    nodes_left: Option<u64>,
+}
+
+impl Links {
+    fn new(problem: &ExactCover) -> Self {
+        let headers = problem.num_columns + 1;
+        let mut links = Self {
+            left: (0..headers).map(|i| i.wrapping_sub(1)).collect(),
+            right: (0..headers).map(|i| i + 1).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec![usize::MAX; headers],
+            size: vec![0; headers],
+            nodes_left: problem.node_budget,
+        };
+
+        let last_primary = problem.num_primary;
+        links.left[0] = last_primary;
+        links.right[last_primary] = 0;
+        for h in last_primary + 1..headers {
+            links.left[h] = h;
+            links.right[h] = h;
+        }
+
+        for (r, columns) in problem.rows.iter().enumerate() {
+            let first = links.column.len();
+            for (i, &c) in columns.iter().enumerate() {
+                let node = links.column.len();
+                let header = c + 1;
+                let next = if i + 1 == columns.len() {
+                    first
+                } else {
+                    node + 1
+                };
+
+                links.left.push(if i == 0 {
+                    node + columns.len() - 1
+                } else {
+                    node - 1
+                });
+                links.right.push(next);
+                links.up.push(links.up[header]);
+                links.down.push(header);
+                links.column.push(header);
+                links.row.push(r);
+
+                let above = links.up[header];
+                links.down[above] = node;
+                links.up[header] = node;
+                links.size[header] += 1;
+            }
+        }
+
+        links
+    }
+
+    fn cover(&mut self, c: usize) {
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = r;
+        self.left[r] = l;
+
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = d;
+                self.up[d] = u;
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                let (u, d) = (self.up[j], self.down[j]);
+                self.down[u] = j;
+                self.up[d] = j;
+                self.size[self.column[j]] += 1;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+
+        let (l, r) = (self.left[c], self.right[c]);
+        self.right[l] = c;
+        self.left[r] = c;
+    }
+
+    fn search(
+        &mut self,
+        partial: &mut Vec<usize>,
+        visit: &mut impl FnMut(&[usize]) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>, BudgetExhausted> {
+        if let Some(nodes_left) = &mut self.nodes_left {
+            *nodes_left = nodes_left.checked_sub(1).ok_or(BudgetExhausted)?;
+        }
+
+        if self.right[0] == 0 {
+            return Ok(visit(partial));
+        }
+
+        // Branch on the primary column with the fewest remaining rows
+        let mut c = self.right[0];
+        let mut j = self.right[c];
+        while j != 0 {
+            if self.size[j] < self.size[c] {
+                c = j;
+            }
+            j = self.right[j];
+        }
+
+        self.cover(c);
+
+        let mut r = self.down[c];
+        let mut flow = ControlFlow::Continue(());
+        while r != c && flow.is_continue() {
+            partial.push(self.row[r]);
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+
+            let result = self.search(partial, visit);
+
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+            partial.pop();
+
+            flow = match result {
+                Ok(flow) => flow,
+                Err(err) => {
+                    self.uncover(c);
+                    return Err(err);
+                }
+            };
+            r = self.down[r];
+        }
+
+        self.uncover(c);
+        Ok(flow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn knuth_example_works() {
+        let mut problem = ExactCover::new(7, 0);
+        for row in [
+            &[2, 4, 5][..],
+            &[0, 3, 6],
+            &[1, 2, 5],
+            &[0, 3],
+            &[1, 6],
+            &[3, 4, 6],
+        ] {
+            problem.add_row(row);
+        }
+
+        let mut solution = problem.first_solution().unwrap().unwrap();
+        solution.sort();
+        assert_eq!(solution, vec![0, 3, 4]);
+        assert_eq!(problem.count_solutions(), Ok(1));
+    }
+
+    // Ranks and files are primary since each needs exactly one queen, while diagonals
+    // are secondary since they hold at most one.
+    fn queens(n: usize) -> ExactCover {
+        let mut problem = ExactCover::new(2 * n, 2 * (2 * n - 1));
+        for r in 0..n {
+            for c in 0..n {
+                let diagonal = 2 * n + r + c;
+                let anti_diagonal = 2 * n + (2 * n - 1) + (r + n - 1 - c);
+                problem.add_row(&[r, n + c, diagonal, anti_diagonal]);
+            }
+        }
+        problem
+    }
+
+    #[test]
+    fn queens_work() {
+        assert_eq!(queens(1).count_solutions(), Ok(1));
+        assert_eq!(queens(3).count_solutions(), Ok(0));
+        assert_eq!(queens(6).solutions().unwrap().len(), 4);
+        assert_eq!(queens(8).count_solutions(), Ok(92));
+    }
+
+    #[test]
+    fn node_budget_works() {
+        let mut problem = queens(8);
+        problem.set_node_budget(Some(100));
+        assert_eq!(problem.count_solutions(), Err(BudgetExhausted));
+        assert!(problem.first_solution().unwrap().is_some());
+    }
+}
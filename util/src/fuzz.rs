@@ -0,0 +1,300 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::OnceLock;
+
+use chumsky::prelude::*;
+
+use crate::InputFile;
+use crate::differential::{candidates, load, save};
+use crate::rng::Rng;
+
+// Feeds mutated copies of `seeds` to `target`, which should parse the input and run it
+// through both parts. Any input may be rejected, but none of them may panic.
+pub struct Fuzz {
+    pub name: &'static str,
+    pub seeds: Vec<String>,
+    pub target: fn(&str),
+}
+
+// Defines a day's fuzz tests inside its `tests` module. `fuzz_parser_and_parts` feeds
+// `iterations()` mutated copies of the seed to the parser and runs `parts` on whatever it
+// accepts, and `fuzz_reproducers_pass` replays the crashes saved under `dir`.
+#[macro_export]
+macro_rules! fuzz_tests {
+    (
+        name: $name:literal,
+        seed: $seed:expr,
+        dir: $dir:expr,
+        parser: $parser:expr,
+        parts: |$input:ident| $parts:expr $(,)?
+    ) => {
+        fn fuzz() -> $crate::fuzz::Fuzz {
+            $crate::fuzz::Fuzz {
+                name: $name,
+                seeds: vec![$seed.to_string()],
+                target: |contents| {
+                    if let Some($input) = $crate::fuzz::parse($parser, contents) {
+                        $parts;
+                    }
+                },
+            }
+        }
+
+        #[test]
+        fn fuzz_parser_and_parts() {
+            fuzz().run($crate::fuzz::iterations(), &$dir);
+        }
+
+        #[test]
+        fn fuzz_reproducers_pass() {
+            fuzz().replay(&$dir);
+        }
+    };
+}
+
+// How many inputs `fuzz_tests!` tries, which is kept small so the tests can run with
+// the rest unless `FUZZ_ITERATIONS` asks for more
+pub fn iterations() -> u64 {
+    env::var("FUZZ_ITERATIONS")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1000)
+}
+
+// Parses like a day would, also rendering the diagnostics for rejected inputs so that
+// reporting them gets fuzzed too
+pub fn parse<'src, T>(
+    parser: impl Parser<'src, &'src str, T, extra::Err<Rich<'src, char>>>,
+    contents: &'src str,
+) -> Option<T> {
+    match parser.parse(contents).into_result() {
+        Ok(parsed) => Some(parsed),
+        Err(errs) => {
+            InputFile::from(contents).write_diagnostics(errs, io::sink());
+            None
+        }
+    }
+}
+
+thread_local! {
+    // Set while `catch_quietly` runs on this thread
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+    // Where the last quiet panic on this thread happened, which tells crashes apart
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// A panic caught by `catch_quietly`
+pub(crate) struct Panic {
+    pub location: String,
+    pub message: String,
+}
+
+// Runs `f`, catching any panic without printing it. The panic hook is only installed
+// once, and panics on other threads still go to the hook that was there before.
+pub(crate) fn catch_quietly<R>(f: impl FnOnce() -> R) -> Result<R, Panic> {
+    static HOOK: OnceLock<()> = OnceLock::new();
+    HOOK.get_or_init(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                return previous(info);
+            }
+            let location = info.location().map(ToString::to_string);
+            LAST_PANIC.with(|last| *last.borrow_mut() = location);
+        }));
+    });
+
+    let quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(quiet);
+
+    result.map_err(|payload| Panic {
+        location: LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or("<unknown>".to_string()),
+        message: message(payload.as_ref()).to_string(),
+    })
+}
+
+pub(crate) fn message(payload: &dyn Any) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "<unknown>"
+    }
+}
+
+impl Fuzz {
+    // Tries `iterations` mutated inputs. The first crash is shrunk while it still panics
+    // in the same place, saved to `reproducers` and reported by panicking.
+    pub fn run(&self, iterations: u64, reproducers: &Path) {
+        assert!(!self.seeds.is_empty(), "fuzzing needs at least one seed");
+        let mut rng = Rng::new(0);
+
+        let mut crash = None;
+        for _ in 0..iterations {
+            let input = self.mutate(&mut rng);
+            if let Some(panic) = self.crash(&input) {
+                let input = self.shrink(&input, &panic);
+                let panic = self.crash(&input).unwrap();
+                crash = Some((input, panic));
+                break;
+            }
+        }
+
+        if let Some((input, panic)) = crash {
+            let path = save(reproducers, self.name, &input);
+            panic!(
+                "{} panicked, shrunk and saved to '{}'\n{input:?}\n{panic}",
+                self.name,
+                path.display()
+            );
+        }
+    }
+
+    // Runs every saved reproducer for this target, which should no longer panic
+    pub fn replay(&self, reproducers: &Path) {
+        for (path, input) in load(reproducers, self.name) {
+            if let Err(payload) = panic::catch_unwind(|| (self.target)(&input)) {
+                panic!(
+                    "{} still panics on '{}': {}",
+                    self.name,
+                    path.display(),
+                    message(payload.as_ref())
+                );
+            }
+        }
+    }
+
+    // Returns where the target panicked and why, if it did. Panics are expected here, so
+    // they aren't printed as they happen.
+    fn crash(&self, input: &str) -> Option<String> {
+        let panic = catch_quietly(|| (self.target)(input)).err()?;
+        Some(format!("{}: {}", panic.location, panic.message))
+    }
+
+    fn shrink(&self, input: &str, panic: &str) -> String {
+        let location = |panic: &str| panic.split(": ").next().unwrap_or_default().to_string();
+        let mut input = input.to_string();
+        while let Some(smaller) = candidates(&input).into_iter().find(|candidate| {
+            self.crash(candidate)
+                .is_some_and(|p| location(&p) == location(panic))
+        }) {
+            input = smaller;
+        }
+        input
+    }
+
+    // A seed with a few random edits. Most edits reuse bytes from the seeds so the result
+    // still looks like the input format, and numbers are swapped for ones near the limits
+    // of the integer types.
+    fn mutate(&self, rng: &mut Rng) -> String {
+        let mut bytes = rng.choose(&self.seeds).as_bytes().to_vec();
+        let max_len = self.seeds.iter().map(String::len).max().unwrap() * 2;
+
+        for _ in 0..rng.range(1..=4) {
+            let len = bytes.len();
+            let at = rng.index(len + 1);
+            let byte = if rng.chance(0.9) && len > 0 {
+                bytes[rng.index(len)]
+            } else {
+                rng.below(256) as u8
+            };
+
+            match rng.below(6) {
+                0 if at < len => bytes[at] = byte,
+                1 => bytes.insert(at, byte),
+                2 if at < len => {
+                    let end = (at + rng.range(1..=8) as usize).min(len);
+                    bytes.drain(at..end);
+                }
+                3 if at < len => {
+                    let end = (at + rng.range(1..=16) as usize).min(len);
+                    let copy = bytes[at..end].to_vec();
+                    let to = rng.index(len + 1);
+                    bytes.splice(to..to, copy);
+                }
+                4 => {
+                    let other = rng.choose(&self.seeds).as_bytes();
+                    let from = rng.index(other.len() + 1);
+                    bytes.truncate(at);
+                    bytes.extend_from_slice(&other[from..]);
+                }
+                _ => {
+                    let number = *rng.choose(&[
+                        "0",
+                        "1",
+                        "255",
+                        "4294967295",
+                        "4294967296",
+                        "18446744073709551615",
+                        "18446744073709551616",
+                    ]);
+                    let digits = bytes[at..]
+                        .iter()
+                        .take_while(|b| b.is_ascii_digit())
+                        .count();
+                    bytes.splice(at..at + digits, number.bytes());
+                }
+            }
+        }
+
+        bytes.truncate(max_len);
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Panics on any line over 100
+    fn fragile(input: &str) {
+        for line in input.lines() {
+            if let Ok(x) = line.parse::<u64>() {
+                assert!(x <= 100, "{x} is too big");
+            }
+        }
+    }
+
+    #[test]
+    fn crashes_are_shrunk_saved_and_replayed() {
+        let dir = std::env::temp_dir().join(format!("fuzz-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let fuzz = Fuzz {
+            name: "fragile",
+            seeds: vec!["1\n2\n3\n".to_string()],
+            target: fragile,
+        };
+        assert!(panic::catch_unwind(|| fuzz.run(1000, &dir)).is_err());
+
+        let saved = load(&dir, "fragile");
+        assert_eq!(saved.len(), 1);
+        let x: u64 = saved[0].1.trim().parse().unwrap();
+        assert!(x > 100);
+        assert!(panic::catch_unwind(|| fuzz.replay(&dir)).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn sturdy_targets_pass() {
+        let fuzz = Fuzz {
+            name: "sturdy",
+            seeds: vec!["1\n2\n3\n".to_string()],
+            target: |input| {
+                let _ = input.lines().map(str::parse::<u64>).count();
+            },
+        };
+        fuzz.run(1000, Path::new("/nonexistent"));
+        fuzz.replay(Path::new("/nonexistent"));
+    }
+}
@@ -0,0 +1,452 @@
+const WORD_BITS: usize = u64::BITS as usize;
+
+// Nullspaces up to this dimension are searched exhaustively
+const MAX_EXHAUSTIVE_NULLITY: usize = 20;
+// Most combinations of nullspace vectors to try before giving up on a larger nullspace
+const MAX_COMBINATIONS: u64 = 1 << 22;
+
+crate::error_enum! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Error {
+        TooManyCombinations { nullity: usize } =>
+            "too many combinations of the {nullity} free columns to search",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(WORD_BITS)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "bit index {i} out of range for length {}",
+            self.len
+        );
+        self.words[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(
+            i < self.len,
+            "bit index {i} out of range for length {}",
+            self.len
+        );
+        let mask = 1 << (i % WORD_BITS);
+        if value {
+            self.words[i / WORD_BITS] |= mask;
+        } else {
+            self.words[i / WORD_BITS] &= !mask;
+        }
+    }
+
+    pub fn xor_with(&mut self, other: &BitVec) {
+        assert_eq!(self.len, other.len, "bit vectors should have equal lengths");
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a ^= b;
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|&i| self.get(i))
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bits: Vec<bool> = iter.into_iter().collect();
+        let mut v = Self::zeros(bits.len());
+        for (i, bit) in bits.into_iter().enumerate() {
+            v.set(i, bit);
+        }
+        v
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix {
+    rows: Vec<BitVec>,
+    cols: usize,
+}
+
+// Reduced row echelon form of `[A | b]`. Only the first `pivots.len()` rows are non-zero
+// on the left-hand side; any remaining row with a set `rhs` bit makes the system inconsistent.
+struct Echelon {
+    rows: Vec<BitVec>,
+    rhs: BitVec,
+    pivots: Vec<usize>,
+}
+
+impl Echelon {
+    fn is_consistent(&self) -> bool {
+        (self.pivots.len()..self.rows.len()).all(|r| !self.rhs.get(r))
+    }
+
+    fn free_columns(&self, cols: usize) -> Vec<usize> {
+        let mut is_pivot = vec![false; cols];
+        for &p in &self.pivots {
+            is_pivot[p] = true;
+        }
+        (0..cols).filter(|&c| !is_pivot[c]).collect()
+    }
+
+    // Sets every free variable to zero, so each pivot variable equals its row's rhs
+    fn particular_solution(&self, cols: usize) -> BitVec {
+        let mut x = BitVec::zeros(cols);
+        for (r, &p) in self.pivots.iter().enumerate() {
+            x.set(p, self.rhs.get(r));
+        }
+        x
+    }
+
+    fn nullspace(&self, cols: usize) -> Vec<BitVec> {
+        self.free_columns(cols)
+            .into_iter()
+            .map(|f| {
+                let mut v = BitVec::zeros(cols);
+                v.set(f, true);
+                for (r, &p) in self.pivots.iter().enumerate() {
+                    if self.rows[r].get(f) {
+                        v.set(p, true);
+                    }
+                }
+                v
+            })
+            .collect()
+    }
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows: vec![BitVec::zeros(cols); rows],
+            cols,
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        self.rows[row].get(col)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        self.rows[row].set(col, value);
+    }
+
+    pub fn mul_vec(&self, x: &BitVec) -> BitVec {
+        assert_eq!(
+            x.len(),
+            self.cols,
+            "vector length should match column count"
+        );
+        self.rows
+            .iter()
+            .map(|row| {
+                let dot = row
+                    .words
+                    .iter()
+                    .zip(&x.words)
+                    .map(|(a, b)| (a & b).count_ones())
+                    .sum::<u32>();
+                dot % 2 == 1
+            })
+            .collect()
+    }
+
+    fn eliminate(&self, b: &BitVec) -> Echelon {
+        assert_eq!(
+            b.len(),
+            self.rows.len(),
+            "rhs length should match row count"
+        );
+        let mut rows = self.rows.clone();
+        let mut rhs = b.clone();
+        let mut pivots = Vec::new();
+
+        for col in 0..self.cols {
+            let r = pivots.len();
+            let Some(found) = (r..rows.len()).find(|&i| rows[i].get(col)) else {
+                continue;
+            };
+
+            rows.swap(r, found);
+            let (a, b) = (rhs.get(r), rhs.get(found));
+            rhs.set(r, b);
+            rhs.set(found, a);
+
+            for i in 0..rows.len() {
+                if i != r && rows[i].get(col) {
+                    let pivot_row = rows[r].clone();
+                    rows[i].xor_with(&pivot_row);
+                    let bit = rhs.get(i) ^ rhs.get(r);
+                    rhs.set(i, bit);
+                }
+            }
+
+            pivots.push(col);
+        }
+
+        Echelon { rows, rhs, pivots }
+    }
+
+    pub fn rank(&self) -> usize {
+        self.eliminate(&BitVec::zeros(self.rows.len())).pivots.len()
+    }
+
+    pub fn nullspace(&self) -> Vec<BitVec> {
+        self.eliminate(&BitVec::zeros(self.rows.len()))
+            .nullspace(self.cols)
+    }
+
+    pub fn solve(&self, b: &BitVec) -> Option<BitVec> {
+        let echelon = self.eliminate(b);
+        echelon
+            .is_consistent()
+            .then(|| echelon.particular_solution(self.cols))
+    }
+
+    // Every solution is a particular solution plus a combination of the nullspace basis.
+    // The combinations are visited in Gray code order so each step is a single xor.
+    pub fn solutions(&self, b: &BitVec) -> impl Iterator<Item = BitVec> {
+        let echelon = self.eliminate(b);
+        let start = echelon
+            .is_consistent()
+            .then(|| echelon.particular_solution(self.cols));
+        let basis = echelon.nullspace(self.cols);
+        let count = u32::try_from(basis.len())
+            .ok()
+            .and_then(|k| 1_u64.checked_shl(k))
+            .unwrap_or(u64::MAX);
+
+        start.into_iter().flat_map(move |mut x| {
+            let basis = basis.clone();
+            (0..count).map(move |i| {
+                if i > 0 {
+                    x.xor_with(&basis[i.trailing_zeros() as usize]);
+                }
+                x.clone()
+            })
+        })
+    }
+
+    // The solution with the fewest ones. Only combinations of the nullspace basis are
+    // searched, so this fails when the nullspace is too large to search within the limit.
+    pub fn min_weight_solution(&self, b: &BitVec) -> Result<Option<BitVec>, Error> {
+        let echelon = self.eliminate(b);
+        if !echelon.is_consistent() {
+            return Ok(None);
+        }
+
+        let x = echelon.particular_solution(self.cols);
+        let basis = echelon.nullspace(self.cols);
+        if basis.len() <= MAX_EXHAUSTIVE_NULLITY {
+            Ok(Some(Self::min_weight_in_coset(x, &basis)))
+        } else {
+            Self::min_weight_by_size(x, &basis).map(Some)
+        }
+    }
+
+    fn min_weight_in_coset(mut x: BitVec, basis: &[BitVec]) -> BitVec {
+        let mut best = x.clone();
+        let mut best_weight = x.count_ones();
+
+        for i in 1..1_u64 << basis.len() {
+            x.xor_with(&basis[i.trailing_zeros() as usize]);
+            let weight = x.count_ones();
+            if weight < best_weight {
+                best_weight = weight;
+                best = x.clone();
+            }
+        }
+
+        best
+    }
+
+    // Each basis vector is the only one with its free column set, and the particular
+    // solution has none of them, so adding `size` basis vectors gives at least `size` ones.
+    // Trying the combinations from smallest to largest can stop once `size` reaches the
+    // best weight found.
+    fn min_weight_by_size(x: BitVec, basis: &[BitVec]) -> Result<BitVec, Error> {
+        let mut best_weight = x.count_ones();
+        let mut best = x.clone();
+        let mut tried = 0_u64;
+
+        for size in 1..=basis.len() {
+            if size >= best_weight {
+                break;
+            }
+
+            let mut chosen: Vec<usize> = (0..size).collect();
+            // sums[j] is the particular solution plus the first `j` chosen vectors, which
+            // only need updating from the first choice that changed
+            let mut sums = vec![x.clone(); size + 1];
+            let mut changed = 0;
+            loop {
+                tried += 1;
+                if tried > MAX_COMBINATIONS {
+                    return Err(Error::TooManyCombinations {
+                        nullity: basis.len(),
+                    });
+                }
+
+                for j in changed..size {
+                    let (done, rest) = sums.split_at_mut(j + 1);
+                    rest[0].clone_from(&done[j]);
+                    rest[0].xor_with(&basis[chosen[j]]);
+                }
+                let weight = sums[size].count_ones();
+                if weight < best_weight {
+                    best_weight = weight;
+                    best = sums[size].clone();
+                }
+
+                // The next combination in lexicographic order
+                let Some(i) = (0..size)
+                    .rev()
+                    .find(|&i| chosen[i] < basis.len() - size + i)
+                else {
+                    break;
+                };
+                chosen[i] += 1;
+                for j in i + 1..size {
+                    chosen[j] = chosen[j - 1] + 1;
+                }
+                changed = i;
+            }
+        }
+
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(rows: &[&str]) -> Matrix {
+        let mut m = Matrix::zeros(rows.len(), rows[0].len());
+        for (r, row) in rows.iter().enumerate() {
+            for (c, ch) in row.chars().enumerate() {
+                m.set(r, c, ch == '1');
+            }
+        }
+        m
+    }
+
+    fn bits(s: &str) -> BitVec {
+        s.chars().map(|c| c == '1').collect()
+    }
+
+    #[test]
+    fn rank_and_nullspace_work() {
+        let m = matrix(&["1100", "0110", "1010"]);
+        assert_eq!(m.rank(), 2);
+
+        let basis = m.nullspace();
+        assert_eq!(basis.len(), 2);
+        for v in &basis {
+            assert!(m.mul_vec(v).is_zero());
+        }
+    }
+
+    #[test]
+    fn solutions_work() {
+        let m = matrix(&["1100", "0110", "1010"]);
+        let b = bits("101");
+        let solutions: Vec<_> = m.solutions(&b).collect();
+        assert_eq!(solutions.len(), 4);
+        for x in &solutions {
+            assert_eq!(m.mul_vec(x), b);
+        }
+
+        assert!(m.solve(&bits("100")).is_none());
+        assert_eq!(m.solutions(&bits("100")).count(), 0);
+    }
+
+    #[test]
+    fn min_weight_solution_works() {
+        // Both strategies should agree with an exhaustive search
+        for rows in [&["110111", "011010"][..], &["1001", "0101", "0011"][..]] {
+            let m = matrix(rows);
+            for t in 0..1_u32 << m.num_rows() {
+                let b: BitVec = (0..m.num_rows()).map(|i| t & (1 << i) != 0).collect();
+                let expected = m.solutions(&b).map(|x| x.count_ones()).min();
+                let actual = m.min_weight_solution(&b).unwrap();
+                assert_eq!(actual.as_ref().map(BitVec::count_ones), expected);
+                if let Some(x) = actual {
+                    assert_eq!(m.mul_vec(&x), b);
+                }
+
+                let echelon = m.eliminate(&b);
+                if echelon.is_consistent() {
+                    let x = echelon.particular_solution(m.num_cols());
+                    let by_size =
+                        Matrix::min_weight_by_size(x, &echelon.nullspace(m.num_cols())).unwrap();
+                    assert_eq!(Some(by_size.count_ones()), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn min_weight_solution_searches_large_nullspaces() {
+        // Four lights and a hundred buttons that each toggle one of them
+        let mut m = Matrix::zeros(4, 100);
+        for c in 0..100 {
+            m.set(c % 4, c, true);
+        }
+        let b = bits("1011");
+        let x = m.min_weight_solution(&b).unwrap().unwrap();
+        assert_eq!(x.count_ones(), 3);
+        assert_eq!(m.mul_vec(&x), b);
+
+        // Sixty lights, which need thirty buttons that each toggle a neighboring pair. The
+        // forty free columns have far too many combinations of that size.
+        let mut m = Matrix::zeros(60, 100);
+        for c in 0..60 {
+            m.set(c, c, true);
+        }
+        for c in 0..40 {
+            m.set(c, 60 + c, true);
+            m.set(c + 1, 60 + c, true);
+        }
+        let b: BitVec = (0..60).map(|_| true).collect();
+        assert_eq!(
+            m.min_weight_solution(&b),
+            Err(Error::TooManyCombinations { nullity: 40 })
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use std::cmp::Ordering;
+use std::ops::{Mul, Sub};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Ratio {
+    num: i128,
+    den: i128,
+}
+
+impl Ratio {
+    fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "ratio should have a non-zero denominator");
+        let g = gcd(num.unsigned_abs(), den.unsigned_abs()) as i128;
+        let sign = den.signum();
+        Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        }
+    }
+
+    fn integer(n: i128) -> Self {
+        Self { num: n, den: 1 }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+
+    fn floor(self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    fn ceil(self) -> i128 {
+        -(-self.num).div_euclid(self.den)
+    }
+
+    fn recip(self) -> Self {
+        Self::new(self.den, self.num)
+    }
+}
+
+impl Sub for Ratio {
+    type Output = Ratio;
+
+    fn sub(self, rhs: Ratio) -> Ratio {
+        Ratio::new(self.num * rhs.den - rhs.num * self.den, self.den * rhs.den)
+    }
+}
+
+impl Mul for Ratio {
+    type Output = Ratio;
+
+    fn mul(self, rhs: Ratio) -> Ratio {
+        Ratio::new(self.num * rhs.num, self.den * rhs.den)
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+// After elimination every pivot variable is `rhs - sum(coeffs[f] * x[f])` over the free
+// variables, so the search only has to pick values for the free ones.
+struct Reduced {
+    pivots: Vec<(usize, Ratio, Vec<Ratio>)>,
+    free: Vec<usize>,
+    bounds: Vec<u64>,
+}
+
+impl Reduced {
+    fn pivot_values(&self, assigned: &[u64]) -> Option<Vec<(usize, u64)>> {
+        self.pivots
+            .iter()
+            .map(|(col, rhs, coeffs)| {
+                let value = coeffs
+                    .iter()
+                    .zip(assigned)
+                    .fold(*rhs, |acc, (&c, &x)| acc - c * Ratio::integer(x.into()));
+                let value = u64::try_from(value.to_integer()?).ok()?;
+                (value <= self.bounds[*col]).then_some((*col, value))
+            })
+            .collect()
+    }
+
+    // The last free variable is solved as an interval instead of enumerated, since every
+    // pivot constraint `0 <= pivot <= bound` is linear in it once the others are fixed.
+    fn last_free_range(&self, assigned: &[u64]) -> Option<(u64, u64)> {
+        let last = assigned.len();
+        let mut lo = Ratio::integer(0);
+        let mut hi = Ratio::integer(self.bounds[self.free[last]].into());
+
+        for (col, rhs, coeffs) in &self.pivots {
+            let rest = coeffs[..last]
+                .iter()
+                .zip(assigned)
+                .fold(*rhs, |acc, (&c, &x)| acc - c * Ratio::integer(x.into()));
+            let bound = Ratio::integer(self.bounds[*col].into());
+            let coeff = coeffs[last];
+
+            // Need 0 <= rest - coeff * x <= bound
+            match coeff.cmp(&Ratio::integer(0)) {
+                Ordering::Equal => {
+                    if rest < Ratio::integer(0) || rest > bound {
+                        return None;
+                    }
+                }
+                Ordering::Greater => {
+                    hi = hi.min(rest * coeff.recip());
+                    lo = lo.max((rest - bound) * coeff.recip());
+                }
+                Ordering::Less => {
+                    lo = lo.max(rest * coeff.recip());
+                    hi = hi.min((rest - bound) * coeff.recip());
+                }
+            }
+        }
+
+        let lo = u64::try_from(lo.ceil()).ok()?;
+        let hi = u64::try_from(hi.floor()).ok()?;
+        (lo <= hi).then_some((lo, hi))
+    }
+
+    fn search(&self, assigned: &mut Vec<u64>, best: &mut Option<(u64, Vec<u64>)>) {
+        let partial: u64 = assigned.iter().sum();
+        if best.as_ref().is_some_and(|(total, _)| partial >= *total) {
+            return;
+        }
+
+        if assigned.len() + 1 < self.free.len() {
+            for x in 0..=self.bounds[self.free[assigned.len()]] {
+                assigned.push(x);
+                self.search(assigned, best);
+                assigned.pop();
+            }
+            return;
+        }
+
+        let range = if self.free.is_empty() {
+            Some((0, 0))
+        } else {
+            self.last_free_range(assigned)
+        };
+
+        let Some((lo, hi)) = range else {
+            return;
+        };
+
+        // The total is linear in the last free variable, so walking from the cheaper end
+        // means the first feasible value is the best one for this branch.
+        let slope = self
+            .pivots
+            .iter()
+            .fold(Ratio::integer(1), |acc, (_, _, coeffs)| {
+                coeffs.last().map_or(acc, |&c| acc - c)
+            });
+        let values: Box<dyn Iterator<Item = u64>> = if slope >= Ratio::integer(0) {
+            Box::new(lo..=hi)
+        } else {
+            Box::new((lo..=hi).rev())
+        };
+
+        for x in values {
+            if !self.free.is_empty() {
+                assigned.push(x);
+            }
+
+            let pivots = self.pivot_values(assigned);
+            if let Some(pivots) = &pivots {
+                let total =
+                    assigned.iter().sum::<u64>() + pivots.iter().map(|(_, v)| v).sum::<u64>();
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_total, _)| total < *best_total)
+                {
+                    let mut solution = vec![0; self.bounds.len()];
+                    for (&col, &value) in self.free.iter().zip(assigned.iter()) {
+                        solution[col] = value;
+                    }
+                    for &(col, value) in pivots {
+                        solution[col] = value;
+                    }
+                    *best = Some((total, solution));
+                }
+            }
+
+            if !self.free.is_empty() {
+                assigned.pop();
+            }
+
+            if pivots.is_some() {
+                break;
+            }
+        }
+    }
+}
+
+// Finds non-negative integers `x` minimizing `sum(x)` subject to `a * x == b`. Coefficients
+// must be non-negative so that every variable is bounded by the right-hand side, which keeps
+// the search over the free variables finite. Meant for small systems like day10's machines.
+pub fn min_sum_solution(a: &[Vec<u64>], b: &[u64]) -> Option<Vec<u64>> {
+    assert_eq!(a.len(), b.len(), "matrix should have a row per rhs entry");
+    let cols = a.first().map_or(0, Vec::len);
+    assert!(
+        a.iter().all(|row| row.len() == cols),
+        "matrix rows should have equal lengths"
+    );
+
+    // A column that appears in no row never helps, so it is pinned to zero
+    let bounds: Vec<u64> = (0..cols)
+        .map(|j| {
+            (0..a.len())
+                .filter(|&i| a[i][j] > 0)
+                .map(|i| b[i] / a[i][j])
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut rows: Vec<Vec<Ratio>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, &rhs)| {
+            row.iter()
+                .chain(std::iter::once(&rhs))
+                .map(|&v| Ratio::integer(v.into()))
+                .collect()
+        })
+        .collect();
+
+    let mut pivot_cols = Vec::new();
+    for col in 0..cols {
+        let r = pivot_cols.len();
+        let Some(found) = (r..rows.len()).find(|&i| !rows[i][col].is_zero()) else {
+            continue;
+        };
+
+        rows.swap(r, found);
+        let scale = rows[r][col].recip();
+        for v in rows[r].iter_mut() {
+            *v = *v * scale;
+        }
+
+        for i in 0..rows.len() {
+            if i != r && !rows[i][col].is_zero() {
+                let factor = rows[i][col];
+                let pivot_row = rows[r].clone();
+                for (v, &p) in rows[i].iter_mut().zip(&pivot_row) {
+                    *v = *v - factor * p;
+                }
+            }
+        }
+
+        pivot_cols.push(col);
+    }
+
+    if rows[pivot_cols.len()..]
+        .iter()
+        .any(|row| !row[cols].is_zero())
+    {
+        return None;
+    }
+
+    // Enumerate the tightly bounded free variables first and leave the loosest for last,
+    // since the last one is solved as an interval.
+    let mut free: Vec<usize> = (0..cols).filter(|c| !pivot_cols.contains(c)).collect();
+    free.sort_by_key(|&c| bounds[c]);
+
+    let pivots = pivot_cols
+        .iter()
+        .enumerate()
+        .map(|(r, &col)| {
+            let coeffs = free.iter().map(|&f| rows[r][f]).collect();
+            (col, rows[r][cols], coeffs)
+        })
+        .collect();
+
+    let reduced = Reduced {
+        pivots,
+        free,
+        bounds,
+    };
+
+    let mut best = None;
+    reduced.search(&mut Vec::new(), &mut best);
+    best.map(|(_, solution)| solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_sum_solution_works() {
+        // x0 + x1 = 3, x1 + x2 = 5: best is x1 = 3, x2 = 2
+        let a = vec![vec![1, 1, 0], vec![0, 1, 1]];
+        assert_eq!(min_sum_solution(&a, &[3, 5]), Some(vec![0, 3, 2]));
+
+        // 2 * x0 = 3 has no integer solution
+        assert_eq!(min_sum_solution(&[vec![2]], &[3]), None);
+
+        // x0 + x1 = 1, x0 + x1 = 2 is inconsistent
+        assert_eq!(min_sum_solution(&[vec![1, 1], vec![1, 1]], &[1, 2]), None);
+    }
+
+    #[test]
+    fn min_sum_solution_matches_exhaustive_search() {
+        let a = vec![
+            vec![1, 0, 1, 1, 0],
+            vec![0, 1, 1, 0, 1],
+            vec![1, 1, 0, 0, 2],
+        ];
+        for b in [[4, 6, 5], [7, 3, 9], [0, 0, 0], [5, 5, 1]] {
+            let mut expected = None;
+            for n in 0..10_u64.pow(5) {
+                let x: Vec<u64> = (0..5).map(|i| n / 10_u64.pow(i) % 10).collect();
+                let satisfied = a
+                    .iter()
+                    .zip(&b)
+                    .all(|(row, &rhs)| row.iter().zip(&x).map(|(c, v)| c * v).sum::<u64>() == rhs);
+                if satisfied {
+                    let total: u64 = x.iter().sum();
+                    expected = Some(expected.map_or(total, |e: u64| e.min(total)));
+                }
+            }
+
+            let actual = min_sum_solution(&a, &b).map(|x| x.iter().sum::<u64>());
+            assert_eq!(actual, expected, "rhs {b:?}");
+        }
+    }
+}
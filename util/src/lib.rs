@@ -1,12 +1,46 @@
 use anyhow::{Context, Result};
-use ariadne::{Color, Label, Report, ReportKind, sources};
+use ariadne::{Color, Config, IndexType, Label, Report, ReportKind, sources};
 use chumsky::prelude::*;
+use std::cell::RefCell;
 use std::ffi::OsString;
 use std::fs;
+use std::io::{self, Write};
+use std::ops::Deref;
 use std::path::Path;
 use std::str::FromStr;
 
+pub mod automaton;
 pub mod collections;
+pub mod day;
+pub mod differential;
+pub mod digit_dp;
+pub mod error;
+pub mod exact_cover;
+pub mod fuzz;
+pub mod gf2;
+pub mod ilp;
+pub mod pairs;
+pub mod parallel;
+pub mod polyomino;
+pub mod rng;
+pub mod runner;
+
+// Used by `aoc_day!` and `aoc_main!` so days don't need their own dependencies
+pub use anyhow;
+pub use inventory;
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+// Runs `f`, collecting the diagnostics it prints on this thread instead of writing them
+// to stderr
+pub(crate) fn capture_diagnostics<R>(f: impl FnOnce() -> R) -> (R, String) {
+    let outer = CAPTURED.replace(Some(Vec::new()));
+    let result = f();
+    let captured = CAPTURED.replace(outer).unwrap_or_default();
+    (result, String::from_utf8_lossy(&captured).into_owned())
+}
 
 pub struct InputFile {
     pub path: OsString,
@@ -14,8 +48,8 @@ pub struct InputFile {
 }
 
 impl InputFile {
-    pub fn read(relative_path: &str) -> Result<Self> {
-        let path = Path::new(relative_path).to_path_buf();
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
         let contents = fs::read_to_string(&path)
             .with_context(|| format!("Failed to read input file '{}'", path.display()))?;
 
@@ -25,7 +59,29 @@ impl InputFile {
         })
     }
 
+    // An input compiled into the binary, named after the file it was read from so
+    // diagnostics still say which input they're about
+    pub fn embedded(path: &str, contents: &str) -> Self {
+        Self {
+            path: OsString::from(format!("{path} (embedded)")),
+            contents: contents.to_string(),
+        }
+    }
+
+    // Points at `span` in the input, for problems found after parsing
+    pub fn report(&self, span: SimpleSpan, message: impl ToString) {
+        self.print_diagnostics(vec![Rich::custom(span, message)]);
+    }
+
+    // Writes to stderr, unless `capture_diagnostics` is collecting them on this thread
     pub fn print_diagnostics<'src>(&self, errs: Vec<Rich<'src, char>>) {
+        CAPTURED.with_borrow_mut(|captured| match captured {
+            Some(out) => self.write_diagnostics(errs, out),
+            None => self.write_diagnostics(errs, io::stderr()),
+        });
+    }
+
+    pub fn write_diagnostics<'src>(&self, errs: Vec<Rich<'src, char>>, mut out: impl Write) {
         let escape = |s: String| {
             s.replace("\r", "\\r")
                 .replace("\n", "\\n")
@@ -37,6 +93,8 @@ impl InputFile {
                 ReportKind::Error,
                 (self.path.display().to_string(), err.span().into_range()),
             )
+            // Spans from chumsky count bytes, not chars
+            .with_config(Config::default().with_index_type(IndexType::Byte))
             .with_message(escape(err.to_string()))
             .with_label(
                 Label::new((self.path.display().to_string(), err.span().into_range()))
@@ -44,10 +102,10 @@ impl InputFile {
                     .with_color(Color::Red),
             )
             .finish()
-            .eprint(sources([(
-                self.path.display().to_string(),
-                self.contents.clone(),
-            )]))
+            .write(
+                sources([(self.path.display().to_string(), self.contents.clone())]),
+                &mut out,
+            )
             .unwrap();
         }
     }
@@ -70,3 +128,54 @@ where
 {
     text::int(radix).try_map(|s: &str, span| s.parse::<T>().map_err(|e| Rich::custom(span, e)))
 }
+
+// A parsed value along with the part of the input it came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: SimpleSpan,
+}
+
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+pub fn spanned<'a, T>(
+    parser: impl Parser<'a, &'a str, T, extra::Err<Rich<'a, char>>>,
+) -> impl Parser<'a, &'a str, Spanned<T>, extra::Err<Rich<'a, char>>> {
+    parser.map_with(|value, e| Spanned {
+        value,
+        span: e.span(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_point_into_the_input() {
+        let contents = "12 345\n7";
+        let numbers = spanned(unsigned::<u32>(10))
+            .separated_by(text::whitespace().at_least(1))
+            .collect::<Vec<_>>()
+            .parse(contents)
+            .into_result()
+            .unwrap();
+
+        let found: Vec<_> = numbers
+            .iter()
+            .map(|n| (**n, &contents[n.span.into_range()]))
+            .collect();
+        assert_eq!(found, [(12, "12"), (345, "345"), (7, "7")]);
+
+        let mut out = Vec::new();
+        let file = InputFile::from(contents);
+        file.write_diagnostics(vec![Rich::custom(numbers[1].span, "too big")], &mut out);
+        assert!(String::from_utf8(out).unwrap().contains("too big"));
+    }
+}
@@ -0,0 +1,211 @@
+use crate::parallel;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BestPair<S> {
+    pub i: usize,
+    pub j: usize,
+    pub score: S,
+}
+
+// Every unordered pair with its upper bound, best bound first. Ties keep index order so
+// the search is deterministic.
+fn candidates<T, S: Ord>(items: &[T], bound: impl Fn(&T, &T) -> S) -> Vec<(S, usize, usize)> {
+    let mut candidates: Vec<_> = (0..items.len())
+        .flat_map(|i| (i + 1..items.len()).map(move |j| (i, j)))
+        .map(|(i, j)| (bound(&items[i], &items[j]), i, j))
+        .collect();
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+    candidates
+}
+
+// Finds the pair with the highest score, where `score` returns `None` for pairs that don't
+// count. `bound` must never be below the score of a pair. Pairs are scored in order of
+// their bound, so once a bound can't beat the best score so far the rest are skipped.
+pub fn best_pair<T, S: Ord + Copy>(
+    items: &[T],
+    bound: impl Fn(&T, &T) -> S,
+    score: impl Fn(&T, &T) -> Option<S>,
+) -> Option<BestPair<S>> {
+    let mut best: Option<BestPair<S>> = None;
+
+    for (b, i, j) in candidates(items, bound) {
+        if best.is_some_and(|best| b <= best.score) {
+            break;
+        }
+        if let Some(score) = score(&items[i], &items[j])
+            && best.is_none_or(|best| score > best.score)
+        {
+            best = Some(BestPair { i, j, score });
+        }
+    }
+
+    best
+}
+
+// Same as `best_pair`, but scores pairs with `parallel::map`. Each round gives every thread
+// a block of candidates to score in order, starting from the best score of the rounds
+// before, so the pruning still applies. Returns the same pair as `best_pair`.
+pub fn best_pair_parallel<T: Sync, S: Ord + Copy + Send + Sync>(
+    items: &[T],
+    bound: impl Fn(&T, &T) -> S,
+    score: impl Fn(&T, &T) -> Option<S> + Sync + Send,
+) -> Option<BestPair<S>> {
+    const BLOCK: usize = 1024;
+
+    let candidates = candidates(items, bound);
+    // The best score with the position of its candidate
+    let mut best: Option<(S, usize)> = None;
+
+    let round_len = BLOCK * parallel::threads();
+    for (r, round) in candidates.chunks(round_len).enumerate() {
+        if best.is_some_and(|(s, _)| round[0].0 < s) {
+            break;
+        }
+
+        let blocks: Vec<_> = round.chunks(BLOCK).collect();
+        let found = parallel::map(&blocks, |n, block| {
+            let mut local = best;
+            for (m, &(b, i, j)) in block.iter().enumerate() {
+                if local.is_some_and(|(s, _)| b < s) {
+                    break;
+                }
+                if let Some(s) = score(&items[i], &items[j])
+                    && local.is_none_or(|(ls, _)| s > ls)
+                {
+                    local = Some((s, r * round_len + n * BLOCK + m));
+                }
+            }
+            local
+        });
+
+        // Blocks are in order, so ties go to the earliest candidate
+        for (s, k) in found.into_iter().flatten() {
+            if best.is_none_or(|(bs, _)| s > bs) {
+                best = Some((s, k));
+            }
+        }
+    }
+
+    best.map(|(score, k)| {
+        let (_, i, j) = candidates[k];
+        BestPair { i, j, score }
+    })
+}
+
+// The points with no other point both left of and below them, from left to right. Any
+// rectangle whose lower left corner is a point can be grown to one whose corner is here.
+pub fn staircase(points: &[(u64, u64)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by_key(|&i| points[i]);
+
+    let mut result = Vec::new();
+    let mut lowest = u64::MAX;
+    for i in order {
+        let (_, y) = points[i];
+        if result.is_empty() || y < lowest {
+            lowest = y;
+            result.push(i);
+        }
+    }
+    result
+}
+
+// Finds the two points spanning the rectangle with the most lattice points, counting its
+// border. The corners of the best rectangle lie on opposite staircases of the orthogonal
+// hull, so only those pairs are compared.
+pub fn max_rectangle(points: &[(u64, u64)]) -> Option<BestPair<u128>> {
+    let area = |a: (u64, u64), b: (u64, u64)| {
+        (u128::from(a.0.abs_diff(b.0)) + 1) * (u128::from(a.1.abs_diff(b.1)) + 1)
+    };
+
+    // Reflecting the y coordinate turns the upper left and lower right staircases into the
+    // lower left and upper right ones
+    let mut best: Option<BestPair<u128>> = None;
+    for reflect in [false, true] {
+        let reflected: Vec<_> = points
+            .iter()
+            .map(|&(x, y)| (x, if reflect { u64::MAX - y } else { y }))
+            .collect();
+        let flipped: Vec<_> = reflected
+            .iter()
+            .map(|&(x, y)| (u64::MAX - x, u64::MAX - y))
+            .collect();
+
+        for &a in &staircase(&reflected) {
+            for &b in &staircase(&flipped) {
+                if a == b {
+                    continue;
+                }
+                let score = area(points[a], points[b]);
+                if best.is_none_or(|best| score > best.score) {
+                    best = Some(BestPair {
+                        i: a.min(b),
+                        j: a.max(b),
+                        score,
+                    });
+                }
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    // Deterministic points without pulling in a random number generator
+    fn points(n: u64, seed: u64) -> Vec<(u64, u64)> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % 1000
+        };
+        (0..n).map(|_| (next(), next())).collect()
+    }
+
+    #[test]
+    fn max_rectangle_matches_all_pairs() {
+        for seed in 0..20 {
+            let points = points(50, seed);
+            let expected = (0..points.len())
+                .flat_map(|i| (i + 1..points.len()).map(move |j| (i, j)))
+                .map(|(i, j)| {
+                    let (a, b) = (points[i], points[j]);
+                    u128::from(a.0.abs_diff(b.0) + 1) * u128::from(a.1.abs_diff(b.1) + 1)
+                })
+                .max();
+            assert_eq!(max_rectangle(&points).map(|b| b.score), expected);
+        }
+
+        assert_eq!(max_rectangle(&[(3, 4)]), None);
+    }
+
+    #[test]
+    fn best_pair_prunes() {
+        let items: Vec<u64> = (1..=100).collect();
+        let scored = AtomicUsize::new(0);
+        let score = |a: &u64, b: &u64| {
+            scored.fetch_add(1, Ordering::Relaxed);
+            // Only pairs with an even sum count
+            (a + b).is_multiple_of(2).then_some(a + b)
+        };
+
+        let best = best_pair(&items, |a, b| a + b, score).unwrap();
+        assert_eq!((best.i, best.j, best.score), (97, 99, 198));
+        assert!(scored.load(Ordering::Relaxed) < 10);
+
+        let parallel = best_pair_parallel(&items, |a, b| a + b, score);
+        assert_eq!(parallel, Some(best));
+        let sequential = parallel::sequential(|| best_pair_parallel(&items, |a, b| a + b, score));
+        assert_eq!(sequential, Some(best));
+
+        let none = best_pair(&items, |a, b| a + b, |_, _| None::<u64>);
+        assert_eq!(none, None);
+    }
+}
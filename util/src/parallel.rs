@@ -0,0 +1,127 @@
+use std::cell::Cell;
+
+use anyhow::{Context, Result, bail};
+
+thread_local! {
+    // Set while `sequential` runs on this thread
+    static SEQUENTIAL: Cell<bool> = const { Cell::new(false) };
+}
+
+// Maps every item along with its index. With the `parallel` feature the items are shared
+// out over the rayon thread pool, otherwise they're mapped in turn. Either way the results
+// keep the order of the items, so folding them in order gives the same answer, and the
+// same first error, as a sequential loop.
+#[cfg(feature = "parallel")]
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(usize, &T) -> R + Sync + Send) -> Vec<R> {
+    use rayon::prelude::*;
+
+    if SEQUENTIAL.get() {
+        return items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| f(i, item))
+            .collect();
+    }
+    items
+        .par_iter()
+        .enumerate()
+        .map(|(i, item)| f(i, item))
+        .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(usize, &T) -> R + Sync + Send) -> Vec<R> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| f(i, item))
+        .collect()
+}
+
+// Runs `f` with every `map` it makes on this thread mapping the items in turn, so tests
+// can check that the answers don't depend on the feature
+pub fn sequential<R>(f: impl FnOnce() -> R) -> R {
+    let outer = SEQUENTIAL.replace(true);
+    let result = f();
+    SEQUENTIAL.set(outer);
+    result
+}
+
+// How many threads parallel work should use
+#[cfg(feature = "parallel")]
+pub fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// Takes `--threads <n>` off the front of `args` and sizes the thread pool with it. The pool
+// can only be sized once, before anything runs on it.
+pub fn threads_arg(args: &mut Vec<String>) -> Result<()> {
+    if args.first().is_none_or(|arg| arg != "--threads") {
+        return Ok(());
+    }
+    let threads: usize = args
+        .get(1)
+        .context("Missing value for '--threads'")?
+        .parse()
+        .context("Thread count should be a number")?;
+    if threads == 0 {
+        bail!("Thread count should be positive");
+    }
+    args.drain(..2);
+    set_threads(threads)
+}
+
+#[cfg(feature = "parallel")]
+fn set_threads(threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .context("Failed to start the thread pool")
+}
+
+#[cfg(not(feature = "parallel"))]
+fn set_threads(_: usize) -> Result<()> {
+    bail!("'--threads' needs the parallel feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_keep_their_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let squares = map(&items, |i, &x| (i, x * x));
+        assert!(
+            squares
+                .iter()
+                .enumerate()
+                .all(|(i, &(j, y))| i == j && y == (i * i) as u64)
+        );
+    }
+
+    #[test]
+    fn sequential_maps_stay_on_this_thread() {
+        let items: Vec<u64> = (0..100).collect();
+        let here = std::thread::current().id();
+        let threads = sequential(|| map(&items, |_, _| std::thread::current().id()));
+        assert!(threads.iter().all(|&id| id == here));
+    }
+
+    #[test]
+    fn thread_counts_must_be_numbers() {
+        let mut args = vec!["--batch".to_string(), "inputs".to_string()];
+        threads_arg(&mut args).unwrap();
+        assert_eq!(args.len(), 2);
+
+        let mut args = vec!["--threads".to_string(), "none".to_string()];
+        assert!(threads_arg(&mut args).is_err());
+        let mut args = vec!["--threads".to_string(), "0".to_string()];
+        assert!(threads_arg(&mut args).is_err());
+    }
+}
@@ -0,0 +1,499 @@
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Polyomino {
+    // Sorted in row-major order and shifted so the bounding box starts at (0, 0)
+    cells: Vec<(usize, usize)>,
+}
+
+impl Polyomino {
+    pub fn new(cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let cells: Vec<_> = cells.into_iter().collect();
+        let min_row = cells.iter().map(|&(r, _)| r).min().unwrap_or(0);
+        let min_col = cells.iter().map(|&(_, c)| c).min().unwrap_or(0);
+
+        let mut cells: Vec<_> = cells
+            .into_iter()
+            .map(|(r, c)| (r - min_row, c - min_col))
+            .collect();
+        cells.sort();
+        cells.dedup();
+
+        Self { cells }
+    }
+
+    pub fn from_rows<S: AsRef<str>>(rows: &[S]) -> Self {
+        Self::new(rows.iter().enumerate().flat_map(|(r, row)| {
+            row.as_ref()
+                .char_indices()
+                .filter(|&(_, ch)| ch == '#')
+                .map(move |(c, _)| (r, c))
+        }))
+    }
+
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.iter().map(|&(r, _)| r + 1).max().unwrap_or(0)
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(|&(_, c)| c + 1).max().unwrap_or(0)
+    }
+
+    fn rotate(&self) -> Self {
+        let height = self.height();
+        Self::new(self.cells.iter().map(|&(r, c)| (c, height - 1 - r)))
+    }
+
+    fn reflect(&self) -> Self {
+        let width = self.width();
+        Self::new(self.cells.iter().map(|&(r, c)| (r, width - 1 - c)))
+    }
+
+    // All distinct rotations and reflections
+    pub fn orientations(&self) -> Vec<Polyomino> {
+        let mut result = Vec::new();
+        let mut shape = self.clone();
+
+        for _ in 0..4 {
+            result.push(shape.reflect());
+            shape = shape.rotate();
+            result.push(shape.clone());
+        }
+
+        result.sort();
+        result.dedup();
+        result
+    }
+
+    // How many more cells land on one checkerboard color than the other
+    fn color_imbalance(&self) -> usize {
+        let even = self
+            .cells
+            .iter()
+            .filter(|&&(r, c)| (r + c) % 2 == 0)
+            .count();
+        even.abs_diff(self.area() - even)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    pub piece: usize,
+    pub cells: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Packing {
+    pub width: usize,
+    pub height: usize,
+    pub placements: Vec<Placement>,
+}
+
+impl Packing {
+    fn transpose(self) -> Self {
+        let placements = self
+            .placements
+            .into_iter()
+            .map(|p| Placement {
+                piece: p.piece,
+                cells: p.cells.into_iter().map(|(r, c)| (c, r)).collect(),
+            })
+            .collect();
+
+        Self {
+            width: self.height,
+            height: self.width,
+            placements,
+        }
+    }
+}
+
+impl fmt::Display for Packing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<char> = ('A'..='Z').chain('a'..='z').collect();
+        let mut grid = vec![vec!['.'; self.width]; self.height];
+
+        for (i, placement) in self.placements.iter().enumerate() {
+            for &(r, c) in &placement.cells {
+                grid[r][c] = labels[i % labels.len()];
+            }
+        }
+
+        for row in grid {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Orientation {
+    // Offsets from the first cell in row-major order, so nothing lies before the anchor
+    offsets: Vec<(usize, isize)>,
+}
+
+struct Search {
+    width: usize,
+    height: usize,
+    occupied: Vec<bool>,
+    orientations: Vec<Vec<Orientation>>,
+    remaining: Vec<usize>,
+    placements: Vec<Placement>,
+    // Cells past this many after the scan position can't be occupied yet
+    window: usize,
+    // Most slack each failed state was tried with, since less slack can only be worse
+    failed: HashMap<(usize, Vec<usize>, Vec<u64>), usize>,
+}
+
+impl Search {
+    // Everything before `pos` is final, so the outcome only depends on the occupied cells
+    // inside the window ahead of it and on what is left to place.
+    fn state(&self, pos: usize) -> (usize, Vec<usize>, Vec<u64>) {
+        let end = (pos + self.window).min(self.occupied.len());
+        let mut bits = vec![0; self.window.div_ceil(64)];
+        for (i, _) in self.occupied[pos..end]
+            .iter()
+            .enumerate()
+            .filter(|&(_, &o)| o)
+        {
+            bits[i / 64] |= 1 << (i % 64);
+        }
+        (pos, self.remaining.clone(), bits)
+    }
+
+    fn cells_at(&self, pos: usize, orientation: &Orientation) -> Option<Vec<(usize, usize)>> {
+        let (row, col) = (pos / self.width, pos % self.width);
+        orientation
+            .offsets
+            .iter()
+            .map(|&(dr, dc)| {
+                let r = row + dr;
+                let c = col.checked_add_signed(dc)?;
+                (r < self.height && c < self.width && !self.occupied[r * self.width + c])
+                    .then_some((r, c))
+            })
+            .collect()
+    }
+
+    // Scans cells in row-major order. Each free cell either becomes the first cell of a
+    // piece or is left empty, which spends one cell of slack. Empty cells are skipped in a
+    // loop, so only placing a piece recurses.
+    fn search(&mut self, mut pos: usize, mut slack: usize) -> bool {
+        if self.remaining.iter().all(|&n| n == 0) {
+            return true;
+        }
+
+        // The states left empty on the way here, which have failed once everything after
+        // them has
+        let mut skipped = Vec::new();
+        loop {
+            while pos < self.occupied.len() && self.occupied[pos] {
+                pos += 1;
+            }
+
+            if pos == self.occupied.len() {
+                break;
+            }
+
+            let state = self.state(pos);
+            if self.failed.get(&state).is_some_and(|&s| slack <= s) {
+                break;
+            }
+
+            if self.place(pos, slack) {
+                return true;
+            }
+
+            skipped.push((state, slack));
+            if slack == 0 {
+                break;
+            }
+            pos += 1;
+            slack -= 1;
+        }
+
+        for (state, slack) in skipped {
+            self.failed.insert(state, slack);
+        }
+        false
+    }
+
+    // Tries each remaining piece with its first cell at `pos`
+    fn place(&mut self, pos: usize, slack: usize) -> bool {
+        for piece in 0..self.orientations.len() {
+            if self.remaining[piece] == 0 {
+                continue;
+            }
+
+            for i in 0..self.orientations[piece].len() {
+                let Some(cells) = self.cells_at(pos, &self.orientations[piece][i]) else {
+                    continue;
+                };
+
+                for &(r, c) in &cells {
+                    self.occupied[r * self.width + c] = true;
+                }
+                self.remaining[piece] -= 1;
+                self.placements.push(Placement { piece, cells });
+
+                if self.search(pos + 1, slack) {
+                    return true;
+                }
+
+                let placement = self.placements.pop().unwrap();
+                self.remaining[piece] += 1;
+                for (r, c) in placement.cells {
+                    self.occupied[r * self.width + c] = false;
+                }
+            }
+        }
+        false
+    }
+}
+
+// Places `quantities[i]` copies of `pieces[i]` into a `width` by `height` region without
+// overlaps, allowing any rotation or reflection. Empty cells are allowed.
+pub fn pack(
+    width: usize,
+    height: usize,
+    pieces: &[Polyomino],
+    quantities: &[usize],
+) -> Option<Packing> {
+    assert_eq!(
+        pieces.len(),
+        quantities.len(),
+        "each piece should have a quantity"
+    );
+
+    // Empty pieces take up no room, so there is nothing to place for them
+    if pieces
+        .iter()
+        .zip(quantities)
+        .any(|(p, &q)| p.area() == 0 && q > 0)
+    {
+        let quantities: Vec<usize> = pieces
+            .iter()
+            .zip(quantities)
+            .map(|(p, &q)| if p.area() == 0 { 0 } else { q })
+            .collect();
+        return pack(width, height, pieces, &quantities);
+    }
+
+    // Pieces whose area doesn't even fit in a usize can't fit in any board
+    let board = width.saturating_mul(height);
+    let area = pieces
+        .iter()
+        .zip(quantities)
+        .try_fold(0_usize, |acc, (p, &q)| {
+            acc.checked_add(p.area().checked_mul(q)?)
+        })?;
+    if area > board {
+        return None;
+    }
+
+    // A piece too long for the region in every orientation can't be placed at all
+    if pieces.iter().zip(quantities).any(|(p, &q)| {
+        q > 0
+            && p.orientations()
+                .iter()
+                .all(|o| o.width() > width || o.height() > height)
+    }) {
+        return None;
+    }
+
+    if let Some(packing) = pack_in_blocks(width, height, pieces, quantities) {
+        return Some(packing);
+    }
+
+    if !checkerboard_balances(board, board - area, pieces, quantities) {
+        return None;
+    }
+
+    // The memoized state covers a few rows ahead of the scan, so keep rows short
+    if width > height {
+        let packing = pack(height, width, pieces, quantities)?;
+        return Some(packing.transpose());
+    }
+
+    let orientations = pieces
+        .iter()
+        .map(|piece| {
+            piece
+                .orientations()
+                .iter()
+                .filter(|o| !o.cells.is_empty())
+                .map(|o| {
+                    let (ar, ac) = o.cells[0];
+                    let offsets = o
+                        .cells
+                        .iter()
+                        .map(|&(r, c)| (r - ar, c as isize - ac as isize))
+                        .collect();
+                    Orientation { offsets }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut search = Search {
+        width,
+        height,
+        occupied: vec![false; board],
+        orientations,
+        remaining: quantities.to_vec(),
+        placements: Vec::new(),
+        window: width * pieces.iter().map(Polyomino::height).max().unwrap_or(0),
+        failed: HashMap::new(),
+    };
+
+    let found = search.search(0, board - area);
+    found.then_some(Packing {
+        width,
+        height,
+        placements: search.placements,
+    })
+}
+
+// Every placed piece covers `imbalance` more cells of one checkerboard color than the
+// other, with the sign depending on where it lands. Empty cells have to make up whatever
+// difference is left over, so some choice of signs must come within `slack` of the board.
+fn checkerboard_balances(
+    board: usize,
+    slack: usize,
+    pieces: &[Polyomino],
+    quantities: &[usize],
+) -> bool {
+    let imbalances: Vec<usize> = pieces
+        .iter()
+        .zip(quantities)
+        .flat_map(|(p, &q)| std::iter::repeat_n(p.color_imbalance(), q))
+        .collect();
+    let total: usize = imbalances.iter().sum();
+
+    // reachable[total + x] is whether the signed imbalances can sum to x
+    let mut reachable = vec![false; 2 * total + 1];
+    reachable[total] = true;
+    for d in imbalances {
+        let mut next = vec![false; reachable.len()];
+        for (i, _) in reachable.iter().enumerate().filter(|&(_, &r)| r) {
+            next[i - d] = true;
+            next[i + d] = true;
+        }
+        reachable = next;
+    }
+
+    let board_imbalance = board % 2;
+    reachable
+        .iter()
+        .enumerate()
+        .filter(|&(_, &r)| r)
+        .any(|(i, _)| (total + board_imbalance).abs_diff(i) <= slack)
+}
+
+// When every piece can get its own block of the largest bounding box, no search is needed
+fn pack_in_blocks(
+    width: usize,
+    height: usize,
+    pieces: &[Polyomino],
+    quantities: &[usize],
+) -> Option<Packing> {
+    let block_height = pieces.iter().map(Polyomino::height).max()?;
+    let block_width = pieces.iter().map(Polyomino::width).max()?;
+    let blocks_per_row = width / block_width.max(1);
+    let num_blocks = blocks_per_row.saturating_mul(height / block_height.max(1));
+
+    if quantities
+        .iter()
+        .try_fold(0_usize, |acc, &q| acc.checked_add(q))?
+        > num_blocks
+    {
+        return None;
+    }
+
+    let placements = pieces
+        .iter()
+        .zip(quantities)
+        .enumerate()
+        .flat_map(|(piece, (shape, &q))| std::iter::repeat_n((piece, shape), q))
+        .enumerate()
+        .map(|(block, (piece, shape))| {
+            let row = block / blocks_per_row * block_height;
+            let col = block % blocks_per_row * block_width;
+            Placement {
+                piece,
+                cells: shape
+                    .cells
+                    .iter()
+                    .map(|&(r, c)| (row + r, col + c))
+                    .collect(),
+            }
+        })
+        .collect();
+
+    Some(Packing {
+        width,
+        height,
+        placements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orientations_work() {
+        let square = Polyomino::from_rows(&["##", "##"]);
+        assert_eq!(square.orientations().len(), 1);
+
+        let ell = Polyomino::from_rows(&["#.", "#.", "##"]);
+        assert_eq!(ell.orientations().len(), 8);
+
+        let tee = Polyomino::from_rows(&["###", ".#."]);
+        assert_eq!(tee.orientations().len(), 4);
+    }
+
+    #[test]
+    fn empty_pieces_are_skipped() {
+        let empty = Polyomino::new([]);
+        assert_eq!(empty.area(), 0);
+        let domino = Polyomino::from_rows(&["##"]);
+        let packing = pack(2, 2, &[empty.clone(), domino.clone()], &[3, 2]).unwrap();
+        assert_eq!(packing.placements.len(), 2);
+        assert!(pack(2, 2, &[empty, domino], &[1, 3]).is_none());
+    }
+
+    #[test]
+    fn pack_works() {
+        let ell = Polyomino::from_rows(&["#.", "#.", "##"]);
+        let packing = pack(4, 2, &[ell], &[2]).unwrap();
+        assert_eq!(packing.placements.len(), 2);
+        assert!(!packing.to_string().contains('.'));
+
+        // Five T-tetrominoes always leave the checkerboard colors unbalanced
+        let tee = Polyomino::from_rows(&["###", ".#."]);
+        assert!(pack(5, 4, std::slice::from_ref(&tee), &[5]).is_none());
+        assert!(pack(5, 4, &[tee], &[4]).is_some());
+    }
+
+    #[test]
+    fn long_thin_regions_work() {
+        let plus = Polyomino::from_rows(&[".#.", "###", ".#."]);
+        assert!(pack(20000, 2, std::slice::from_ref(&plus), &[1]).is_none());
+
+        // Too many pieces for a block each, so the search has to leave the four corners
+        // around every plus empty on its way down the region
+        let single = Polyomino::from_rows(&["#"]);
+        let packing = pack(3, 3000, &[plus, single], &[1000, 1]).unwrap();
+        assert_eq!(packing.placements.len(), 1001);
+    }
+}
@@ -0,0 +1,91 @@
+use std::ops::RangeInclusive;
+
+// SplitMix64 (https://prng.di.unimi.it/splitmix64.c). Small, fast and reproducible from a
+// seed, which is all generated puzzle inputs need. Not suitable for anything secure.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // Uses the high bits of a 128-bit product, which is close enough to uniform for
+    // bounds far below 2^64
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound should be positive");
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "range should not be empty");
+        match (hi - lo).checked_add(1) {
+            Some(len) => lo + self.below(len),
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    // True with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < p
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_output() {
+        // First outputs of the reference implementation seeded with 1234567
+        let mut rng = Rng::new(1234567);
+        assert_eq!(rng.next_u64(), 6457827717110365317);
+        assert_eq!(rng.next_u64(), 3203168211198807973);
+    }
+
+    #[test]
+    fn ranges_are_respected() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let x = rng.range(10..=15);
+            assert!((10..=15).contains(&x));
+            seen[(x - 10) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+
+        rng.range(0..=u64::MAX);
+        assert_eq!(rng.range(3..=3), 3);
+
+        let mut items: Vec<u32> = (0..20).collect();
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
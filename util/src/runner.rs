@@ -0,0 +1,305 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+
+use crate::fuzz::catch_quietly;
+use crate::{InputFile, capture_diagnostics, parallel};
+
+// A day's answers to one input, with one answer on days that only have one part
+pub type Solve = fn(&InputFile) -> Result<Vec<String>>;
+
+// One input to solve, which is read from `path` unless it was compiled in
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub path: PathBuf,
+    pub embedded: Option<&'static str>,
+    pub solve: Solve,
+}
+
+impl Job {
+    fn input(&self) -> Result<InputFile> {
+        match self.embedded {
+            Some(contents) => Ok(InputFile::embedded(&self.path.to_string_lossy(), contents)),
+            None => InputFile::read(&self.path),
+        }
+    }
+}
+
+// How one input went in a batch run
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(Vec<String>),
+    // Diagnostics are kept apart so they can be shown after the table instead of being
+    // printed from several threads at once
+    Failed { error: String, diagnostics: String },
+    Panicked(String),
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub path: PathBuf,
+    pub outcome: Outcome,
+    pub time: Duration,
+}
+
+// The inputs named by `--batch <inputs>` when it is the first argument. Days check this
+// before their own arguments.
+pub fn batch_arg(args: &[String]) -> Option<&str> {
+    match args {
+        [flag, inputs, ..] if flag == "--batch" => Some(inputs),
+        _ => None,
+    }
+}
+
+// Solves every input in `inputs` and prints a table of the answers. Inputs that fail or
+// panic are flagged in the table and make the whole batch an error.
+pub fn batch(inputs: &str, solve: Solve) -> Result<()> {
+    let paths = expand(inputs)?;
+    if paths.is_empty() {
+        bail!("No inputs match '{inputs}'");
+    }
+    let jobs: Vec<Job> = paths
+        .into_iter()
+        .map(|path| Job {
+            path,
+            embedded: None,
+            solve,
+        })
+        .collect();
+    report(&jobs)
+}
+
+// Solves each job's input with its own solution and prints a table of the
+// answers like `batch`
+pub fn report(jobs: &[Job]) -> Result<()> {
+    let runs = run(jobs);
+
+    print!("{}", table(&runs));
+    for run in &runs {
+        if let Outcome::Failed { diagnostics, .. } = &run.outcome {
+            eprint!("{diagnostics}");
+        }
+    }
+
+    let failed = runs
+        .iter()
+        .filter(|run| !matches!(run.outcome, Outcome::Solved(..)))
+        .count();
+    if failed > 0 {
+        bail!("{failed} of {} inputs failed", runs.len());
+    }
+    Ok(())
+}
+
+// A file, every file in a directory, or the files matching a pattern with `*` and `?` in
+// its last component, sorted by path. Like in a shell, hidden files only match patterns
+// that start with a dot.
+pub fn expand(inputs: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(inputs);
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let (dir, pattern) = if path.is_dir() {
+        (path, "*")
+    } else {
+        let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+        let pattern = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("No inputs match '{inputs}'"))?;
+        (dir.unwrap_or(Path::new(".")), pattern)
+    };
+
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("Failed to read '{}'", dir.display()))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .filter(|name| !name.starts_with('.') || pattern.starts_with('.'))
+                .is_some_and(|name| matches(pattern.as_bytes(), name.as_bytes()))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+// Whether `name` matches `pattern`, where `*` matches any run of characters and `?` any
+// single one
+fn matches(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, _) => name.is_empty(),
+        (Some((b'*', rest)), _) => {
+            matches(rest, name) || (!name.is_empty() && matches(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name))) => matches(rest, name),
+        (Some((p, rest)), Some((n, name))) => p == n && matches(rest, name),
+        (Some(_), None) => false,
+    }
+}
+
+// Solves the jobs with `parallel::map`, so they share the thread pool with the work inside
+// each solution. Runs come back in the same order as `jobs`.
+pub fn run(jobs: &[Job]) -> Vec<Run> {
+    parallel::map(jobs, |_, job| {
+        // Panics are reported in the table, so they aren't printed as they happen too
+        let start = Instant::now();
+        let (solved, diagnostics) =
+            capture_diagnostics(|| catch_quietly(|| (job.solve)(&job.input()?)));
+        let outcome = match solved {
+            Ok(Ok(answers)) => Outcome::Solved(answers),
+            Ok(Err(err)) => Outcome::Failed {
+                error: format!("{err:#}"),
+                diagnostics,
+            },
+            Err(panic) => Outcome::Panicked(panic.message),
+        };
+
+        Run {
+            path: job.path.clone(),
+            outcome,
+            time: start.elapsed(),
+        }
+    })
+}
+
+pub fn table(runs: &[Run]) -> String {
+    let header = ["input", "part one", "part two", "time", "status"];
+    let rows: Vec<[String; 5]> = runs
+        .iter()
+        .map(|run| {
+            let (one, two, status) = match &run.outcome {
+                Outcome::Solved(answers) => {
+                    let answer = |i: usize| answers.get(i).cloned().unwrap_or("-".into());
+                    (answer(0), answer(1), "ok".to_string())
+                }
+                Outcome::Failed { error, .. } => {
+                    ("-".into(), "-".into(), format!("error: {error}"))
+                }
+                Outcome::Panicked(msg) => ("-".into(), "-".into(), format!("panic: {msg}")),
+            };
+            let time = format!("{:.2?}", run.time);
+            [run.path.display().to_string(), one, two, time, status]
+        })
+        .collect();
+
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut result = String::new();
+    let lines = std::iter::once(header.map(String::from)).chain(rows);
+    for line in lines {
+        // The status is last and can be long, so it isn't padded
+        let [path, one, two, time, status] = line;
+        writeln!(
+            result,
+            "{path:<w0$}  {one:>w1$}  {two:>w2$}  {time:>w3$}  {status}",
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        )
+        .unwrap();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_match_file_names() {
+        assert!(matches(b"day01*.txt", b"day01-alice.txt"));
+        assert!(matches(b"day01*.txt", b"day01.txt"));
+        assert!(matches(b"day0?.txt", b"day07.txt"));
+        assert!(!matches(b"day0?.txt", b"day10.txt"));
+        assert!(!matches(b"*.txt", b"day01.txt.bak"));
+        assert!(matches(b"*", b""));
+    }
+
+    // Sums the numbers in the input, and panics if there is a zero
+    fn solve(file: &InputFile) -> Result<Vec<String>> {
+        let numbers = file
+            .contents
+            .lines()
+            .map(|line| line.parse::<u64>().context("Not a number"))
+            .collect::<Result<Vec<_>>>();
+        let numbers = numbers.inspect_err(|_| file.report((0..1).into(), "not a number"))?;
+        assert!(!numbers.contains(&0), "zero");
+        let sum: u64 = numbers.iter().sum();
+        Ok(vec![sum.to_string(), numbers.len().to_string()])
+    }
+
+    #[test]
+    fn batches_report_every_input() {
+        let dir = std::env::temp_dir().join(format!("runner-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (name, contents) in [
+            ("a.txt", "1\n2\n"),
+            ("b.txt", "x\n"),
+            ("c.txt", "0\n"),
+            ("d.log", "5\n"),
+            (".e.txt", "6\n"),
+        ] {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+
+        let paths = expand(dir.join("*.txt").to_str().unwrap()).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(expand(dir.to_str().unwrap()).unwrap().len(), 4);
+
+        let mut jobs: Vec<Job> = paths
+            .into_iter()
+            .map(|path| Job {
+                path,
+                embedded: None,
+                solve,
+            })
+            .collect();
+        // Compiled-in inputs don't need the file
+        jobs.push(Job {
+            path: dir.join("missing.txt"),
+            embedded: Some("4\n"),
+            solve,
+        });
+
+        let runs = run(&jobs);
+        // The failed input's diagnostics are kept with its outcome
+        let Outcome::Failed { diagnostics, .. } = &runs[1].outcome else {
+            panic!("b.txt should fail");
+        };
+        assert!(diagnostics.contains("b.txt") && diagnostics.contains("not a number"));
+        let outcomes: Vec<_> = runs.iter().map(|run| &run.outcome).collect();
+        assert_eq!(
+            outcomes,
+            [
+                &Outcome::Solved(vec!["3".to_string(), "2".to_string()]),
+                &Outcome::Failed {
+                    error: "Not a number: invalid digit found in string".to_string(),
+                    diagnostics: diagnostics.clone(),
+                },
+                &Outcome::Panicked("zero".to_string()),
+                &Outcome::Solved(vec!["4".to_string(), "1".to_string()]),
+            ]
+        );
+
+        let table = table(&runs);
+        assert_eq!(table.lines().count(), 5);
+        assert!(table.contains("panic: zero"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}